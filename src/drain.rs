@@ -0,0 +1,191 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Heap, Order};
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+
+/// A lazy iterator that removes elements from a [`Heap`] in priority order.
+/// Elements that have not been yielded when the iterator is dropped are removed as well.
+///
+/// This struct is created by [`Heap::drain_sorted`].
+pub struct DrainSorted<'a, T, O, const BRANCHES: usize>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    pub(crate) heap: &'a mut Heap<T, O, BRANCHES>,
+}
+
+impl<'a, T, O, const BRANCHES: usize> Debug for DrainSorted<'a, T, O, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DrainSorted")
+            .field("heap", &self.heap)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Iterator for DrainSorted<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.top()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len: usize = self.heap.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, O, const BRANCHES: usize> ExactSizeIterator for DrainSorted<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+}
+
+impl<T, O, const BRANCHES: usize> FusedIterator for DrainSorted<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+}
+
+impl<T, O, const BRANCHES: usize> Drop for DrainSorted<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+/// A lazy iterator that removes elements from the top of a [`Heap`] for as long as they satisfy a predicate.
+/// Iteration stops at the first element that does not satisfy the predicate, which stays on the heap
+/// along with every element behind it.
+///
+/// This struct is created by [`Heap::drain_while`].
+pub struct DrainWhile<'a, T, O, P, const BRANCHES: usize>
+where
+    T: Copy,
+    O: Order<T>,
+    P: FnMut(&T) -> bool,
+{
+    pub(crate) heap: &'a mut Heap<T, O, BRANCHES>,
+    pub(crate) predicate: P,
+    pub(crate) done: bool,
+}
+
+impl<'a, T, O, P, const BRANCHES: usize> Debug for DrainWhile<'a, T, O, P, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+    P: FnMut(&T) -> bool,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DrainWhile")
+            .field("heap", &self.heap)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, O, P, const BRANCHES: usize> Iterator for DrainWhile<'_, T, O, P, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+    P: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        match self.heap.peek() {
+            Some(x) if (self.predicate)(x) => self.heap.top(),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.heap.len()))
+        }
+    }
+}
+
+impl<T, O, P, const BRANCHES: usize> FusedIterator for DrainWhile<'_, T, O, P, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+    P: FnMut(&T) -> bool,
+{
+}
+
+/// A lazy iterator that pops the element on top of a [`Heap`] along with every element tied with it.
+///
+/// The indices of the ties are collected up front with the same pruned walk as [`Heap::pop_ties`],
+/// and each call to `next` removes one of them, from the highest index down.
+/// This struct is created by [`Heap::pop_equal_iter`].
+pub struct PopEqual<'a, T, O, const BRANCHES: usize>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    pub(crate) heap: &'a mut Heap<T, O, BRANCHES>,
+    pub(crate) ties: std::vec::IntoIter<usize>,
+}
+
+impl<'a, T, O, const BRANCHES: usize> Debug for PopEqual<'a, T, O, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PopEqual")
+            .field("heap", &self.heap)
+            .field("ties", &self.ties)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Iterator for PopEqual<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let index: usize = self.ties.next()?;
+        Some(self.heap.remove(index).unwrap())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ties.size_hint()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> ExactSizeIterator for PopEqual<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+}
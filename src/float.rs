@@ -0,0 +1,360 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Error, ErrorKind, Heap, Order, Result, ReverseOrder};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// A floating point type that can be used as a priority with the [`FloatMin`] and [`FloatMax`] policies.
+pub trait Float: Copy + PartialOrd {
+    /// Returns true if this value is NaN.
+    fn is_nan(self) -> bool;
+}
+
+impl Float for f32 {
+    #[inline]
+    fn is_nan(self) -> bool {
+        f32::is_nan(self)
+    }
+}
+
+impl Float for f64 {
+    #[inline]
+    fn is_nan(self) -> bool {
+        f64::is_nan(self)
+    }
+}
+
+/// Decides where NaN values go in a heap of floats, or whether they are allowed at all.
+pub trait NanPolicy {
+    /// True if NaN values belong on top of the heap, ahead of every other value.
+    const NAN_FIRST: bool;
+    /// True if a [`FloatHeap`] should refuse to store NaN values.
+    const REJECT: bool;
+    /// The policy that places NaN values in the heaps that follow this policy.
+    /// For a policy that rejects NaN values, this only orders values that are never NaN.
+    type Placement: NanPlacement;
+}
+
+/// A [`NanPolicy`] that places NaN values instead of rejecting them.
+///
+/// Only these policies make [`FloatMin`] and [`FloatMax`] an [`Order`], because a plain [`Heap`]
+/// or [`crate::IndexHeap`] has no way to refuse a value. A policy that rejects NaN values can only be
+/// used through a [`FloatHeap`], which checks every value before it is stored.
+///
+/// ## Example:
+///
+/// ```compile_fail
+/// use rheap::{FloatMin, Heap, NanReject};
+///
+/// // a plain heap cannot reject NaN values, so it does not accept a rejecting policy
+/// let heap: Heap<f64, FloatMin<NanReject>, 2> = Heap::new();
+/// ```
+pub trait NanPlacement: NanPolicy {}
+
+/// NaN values are placed on top of the heap, ahead of every other value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NanFirst;
+
+/// NaN values are placed behind every other value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NanLast;
+
+/// NaN values are rejected by [`FloatHeap::insert`] and [`FloatHeap::update`] with [`ErrorKind::NotANumber`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NanReject;
+
+impl NanPolicy for NanFirst {
+    const NAN_FIRST: bool = true;
+    const REJECT: bool = false;
+    type Placement = NanFirst;
+}
+
+impl NanPolicy for NanLast {
+    const NAN_FIRST: bool = false;
+    const REJECT: bool = false;
+    type Placement = NanLast;
+}
+
+impl NanPolicy for NanReject {
+    const NAN_FIRST: bool = false;
+    const REJECT: bool = true;
+    type Placement = NanLast;
+}
+
+impl NanPlacement for NanFirst {}
+
+impl NanPlacement for NanLast {}
+
+/// A policy over floats that names the [`NanPolicy`] it follows and the [`Order`] used to store the values it accepts.
+pub trait FloatOrder<F: Float> {
+    /// The policy used for NaN values.
+    type Nan: NanPolicy;
+    /// The ordering policy of the underlying heap of a [`FloatHeap`].
+    type Ordered: Order<F>;
+}
+
+/// The policy of a minimum heap of floats. The smallest number is on top.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FloatMin<N = NanLast>(PhantomData<N>);
+
+/// The policy of a maximum heap of floats. The largest number is on top.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FloatMax<N = NanLast>(PhantomData<N>);
+
+/// Compares two floats, placing NaN values according to `N` and
+/// every other pair of values according to `ordered`.
+#[inline]
+fn compare_nan<F: Float, N: NanPolicy>(a: F, b: F, ordered: fn(F, F) -> Ordering) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => ordered(a, b),
+        (true, true) => Ordering::Equal,
+        (true, false) if N::NAN_FIRST => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (false, true) if N::NAN_FIRST => Ordering::Greater,
+        (false, true) => Ordering::Less,
+    }
+}
+
+impl<F: Float, N: NanPlacement> Order<F> for FloatMin<N> {
+    #[inline]
+    fn compare(a: &F, b: &F) -> Ordering {
+        compare_nan::<F, N>(*a, *b, |a, b| a.partial_cmp(&b).unwrap())
+    }
+}
+
+impl<F: Float, N: NanPlacement> Order<F> for FloatMax<N> {
+    #[inline]
+    fn compare(a: &F, b: &F) -> Ordering {
+        compare_nan::<F, N>(*a, *b, |a, b| b.partial_cmp(&a).unwrap())
+    }
+}
+
+impl<F: Float, N: NanPolicy> FloatOrder<F> for FloatMin<N> {
+    type Nan = N;
+    type Ordered = FloatMin<N::Placement>;
+}
+
+impl<F: Float, N: NanPolicy> FloatOrder<F> for FloatMax<N> {
+    type Nan = N;
+    type Ordered = FloatMax<N::Placement>;
+}
+
+impl<F: Float, N: NanPlacement> ReverseOrder<F> for FloatMin<N> {
+    type Reverse = FloatMax<N>;
+}
+
+impl<F: Float, N: NanPlacement> ReverseOrder<F> for FloatMax<N> {
+    type Reverse = FloatMin<N>;
+}
+
+/// A heap of floats that enforces the NaN policy of its policy `O`.
+///
+/// When NaN values are allowed to sort first or last, a plain [`Heap`] such as
+/// `Heap<f64, FloatMin<NanLast>, 2>` works just as well. A `FloatHeap` is needed when
+/// NaN values must be kept off the heap, because it checks every value on `insert` and `update`.
+/// The values it accepts are stored in a `Heap<F, O::Ordered, BRANCHES>`.
+/// For the same reason it does not implement [`crate::PriorityQueue`], whose `push` cannot fail.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{ErrorKind, FloatHeap, FloatMin, NanReject};
+///
+/// let mut heap: FloatHeap<f64, FloatMin<NanReject>, 2> = FloatHeap::new();
+/// heap.insert(2.5).unwrap();
+/// heap.insert(-1.0).unwrap();
+/// assert!(heap.insert(f64::NAN).unwrap_err().kind() == ErrorKind::NotANumber);
+/// assert!(heap.top() == Some(-1.0));
+/// ```
+pub struct FloatHeap<F, O, const BRANCHES: usize>
+where
+    F: Float,
+    O: FloatOrder<F>,
+{
+    heap: Heap<F, O::Ordered, BRANCHES>,
+}
+
+impl<F, O, const BRANCHES: usize> Clone for FloatHeap<F, O, BRANCHES>
+where
+    F: Float,
+    O: FloatOrder<F>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+        }
+    }
+}
+
+impl<F, O, const BRANCHES: usize> Debug for FloatHeap<F, O, BRANCHES>
+where
+    F: Float + Debug,
+    O: FloatOrder<F>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FloatHeap")
+            .field("heap", &self.heap)
+            .finish()
+    }
+}
+
+impl<F, O, const BRANCHES: usize> Default for FloatHeap<F, O, BRANCHES>
+where
+    F: Float,
+    O: FloatOrder<F>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F, O, const BRANCHES: usize> TryFrom<&[F]> for FloatHeap<F, O, BRANCHES>
+where
+    F: Float,
+    O: FloatOrder<F>,
+{
+    type Error = Error;
+
+    /// Builds a new heap from a slice of floats.
+    /// Returns an error if the policy rejects NaN values and the slice contains one.
+    fn try_from(s: &[F]) -> Result<Self> {
+        if s.iter().any(|x| !Self::accepts(*x)) {
+            Err(Self::nan_error())
+        } else {
+            Ok(Self {
+                heap: Heap::from(s),
+            })
+        }
+    }
+}
+
+impl<F, O, const BRANCHES: usize> FloatHeap<F, O, BRANCHES>
+where
+    F: Float,
+    O: FloatOrder<F>,
+{
+    /// Constructs a new, empty heap.
+    pub fn new() -> Self {
+        Self { heap: Heap::new() }
+    }
+
+    /// Constructs a new, empty heap with at least the specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            heap: Heap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the underlying heap.
+    pub fn as_heap(&self) -> &Heap<F, O::Ordered, BRANCHES> {
+        &self.heap
+    }
+
+    /// Consumes the `FloatHeap` and returns the underlying heap.
+    pub fn into_heap(self) -> Heap<F, O::Ordered, BRANCHES> {
+        self.heap
+    }
+
+    /// Returns a slice containing the entire underlying vector.
+    pub fn as_slice(&self) -> &[F] {
+        self.heap.as_slice()
+    }
+
+    /// Returns an iterator over the slice.
+    pub fn iter(&self) -> std::slice::Iter<'_, F> {
+        self.heap.iter()
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Clears the heap, removing all elements.
+    pub fn clear(&mut self) {
+        self.heap.clear()
+    }
+
+    /// Returns an immutable reference to the element on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&F> {
+        self.heap.peek()
+    }
+
+    /// Inserts an element into the heap.
+    /// Returns an error if `element` is NaN and the policy rejects NaN values.
+    pub fn insert(&mut self, element: F) -> Result<()> {
+        if Self::accepts(element) {
+            self.heap.insert(element);
+            Ok(())
+        } else {
+            Err(Self::nan_error())
+        }
+    }
+
+    /// Removes and returns the element from the top of the heap. Returns `None` if the heap is empty.
+    pub fn top(&mut self) -> Option<F> {
+        self.heap.top()
+    }
+
+    /// Removes and returns the element at `index`.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<F> {
+        self.heap.remove(index)
+    }
+
+    /// Updates the value of the element at `index`.
+    /// Returns an error if the index is out of bounds, or if the new value is NaN and the
+    /// policy rejects NaN values, in which case the element keeps its old value.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{FloatHeap, FloatMax, NanReject};
+    ///
+    /// let mut heap: FloatHeap<f32, FloatMax<NanReject>, 4> = FloatHeap::new();
+    /// heap.insert(1.0).unwrap();
+    /// heap.insert(3.0).unwrap();
+    /// assert!(heap.update(0, |x| *x = f32::NAN).is_err());
+    /// assert!(heap.peek() == Some(&3.0));
+    /// heap.update(0, |x| *x = 0.5).unwrap();
+    /// assert!(heap.peek() == Some(&1.0));
+    /// ```
+    pub fn update<M>(&mut self, index: usize, modifier: M) -> Result<()>
+    where
+        M: FnOnce(&mut F),
+    {
+        self.heap.check_index(index)?;
+        let mut value: F = self.heap.as_slice()[index];
+        modifier(&mut value);
+        if Self::accepts(value) {
+            self.heap.update(index, |x| *x = value)
+        } else {
+            Err(Self::nan_error())
+        }
+    }
+
+    /// Searches the heap for an element equal to `element` and returns its index, or `None` if it was not found.
+    /// Like [`Heap::find`], the search skips every subtree whose root is already behind `element`.
+    pub fn find(&self, element: &F) -> Option<usize> {
+        self.heap.find(element)
+    }
+
+    fn accepts(value: F) -> bool {
+        !(<O::Nan as NanPolicy>::REJECT && value.is_nan())
+    }
+
+    fn nan_error() -> Error {
+        Error::new(
+            ErrorKind::NotANumber,
+            "NaN values are rejected by this heap's policy.",
+        )
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Heap, Order};
+use std::fmt::Debug;
+use std::ops::{Deref, DerefMut};
+
+/// A guard that gives mutable access to every element of a [`Heap`] as a slice.
+/// The heap property is restored with a single `heap_sort` when the guard is dropped.
+///
+/// This struct is created by [`Heap::as_mut_slice`].
+pub struct SliceMut<'a, T, O, const BRANCHES: usize>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    pub(crate) heap: &'a mut Heap<T, O, BRANCHES>,
+}
+
+impl<'a, T, O, const BRANCHES: usize> Debug for SliceMut<'a, T, O, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SliceMut")
+            .field("heap", &self.heap)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Deref for SliceMut<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.heap.heap
+    }
+}
+
+impl<T, O, const BRANCHES: usize> DerefMut for SliceMut<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.heap.heap
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Drop for SliceMut<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn drop(&mut self) {
+        Heap::<T, O, BRANCHES>::heap_sort(&mut self.heap.heap);
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+/// Receives every change that a heap makes to the positions of its elements, along with the work done to make it.
+///
+/// The sift loops and the insert and remove paths of [`crate::Heap`] report to a hook so that
+/// wrappers can keep undo logs or other side tables in step with the heap. The unit type is the
+/// no-op hook used by the plain heap operations, so they compile to the same code as before.
+pub(crate) trait SiftHook<T> {
+    /// `element` was pushed onto the end of the heap at `index`.
+    #[inline]
+    fn inserted(&mut self, _index: usize, _element: &T) {}
+
+    /// The element at `index`, the last one on the heap, was popped off the end.
+    #[inline]
+    fn removed(&mut self, _index: usize, _element: &T) {}
+
+    /// The elements at `a` and `b` were swapped. `heap` shows the elements after the swap.
+    #[inline]
+    fn swapped(&mut self, _heap: &[T], _a: usize, _b: usize) {}
+
+    /// The element at `index` was modified in place. `old` is its previous value and `heap` shows its new one.
+    #[inline]
+    fn replaced(&mut self, _heap: &[T], _index: usize, _old: &T) {}
+
+    /// Two elements were compared by the ordering policy.
+    #[inline]
+    fn compared(&mut self) {}
+
+    /// A sift loop moved an element one level up or down the tree.
+    #[inline]
+    fn traversed(&mut self) {}
+}
+
+impl<T> SiftHook<T> for () {}
@@ -0,0 +1,369 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Error, ErrorKind, Order, Result};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// Marks a key that is not currently on the heap.
+const ABSENT: usize = usize::MAX;
+
+/// An indexed priority queue whose keys are dense `usize` values, such as node IDs in a graph.
+///
+/// The heap keeps a `pos[key]` array alongside the `key[pos]` array, both maintained
+/// during the d-ary sift loops, so every key based operation runs in O(log n) time
+/// without the O(n) search that [`crate::Heap::find`] requires.
+///
+/// Because the position array is as long as the largest key, a heap built with [`IndexHeap::with_key_limit`]
+/// rejects keys at or past its limit with an `InvalidIndex` error. Any other heap accepts every key except
+/// `usize::MAX`, growing its position array as larger keys are inserted.
+///
+/// It does not implement [`crate::PriorityQueue`] because every element needs a key and `insert` can fail.
+pub struct IndexHeap<P, O, const BRANCHES: usize>
+where
+    O: Order<P>,
+{
+    priorities: Vec<P>,
+    keys: Vec<usize>,
+    pos: Vec<usize>,
+    limit: usize,
+    order: PhantomData<O>,
+}
+
+impl<P, O, const BRANCHES: usize> Clone for IndexHeap<P, O, BRANCHES>
+where
+    O: Order<P>,
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            priorities: self.priorities.clone(),
+            keys: self.keys.clone(),
+            pos: self.pos.clone(),
+            limit: self.limit,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<P, O, const BRANCHES: usize> Debug for IndexHeap<P, O, BRANCHES>
+where
+    O: Order<P>,
+    P: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexHeap")
+            .field("priorities", &self.priorities)
+            .field("keys", &self.keys)
+            .field("pos", &self.pos)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<P, O, const BRANCHES: usize> Default for IndexHeap<P, O, BRANCHES>
+where
+    O: Order<P>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P, O, const BRANCHES: usize> IndexHeap<P, O, BRANCHES>
+where
+    O: Order<P>,
+{
+    /// Constructs a new, empty heap.
+    /// The position array grows as larger keys are inserted.
+    pub fn new() -> Self {
+        Self {
+            priorities: Vec::new(),
+            keys: Vec::new(),
+            pos: Vec::new(),
+            limit: usize::MAX,
+            order: PhantomData,
+        }
+    }
+
+    /// Constructs a new, empty heap that can hold the keys `0..n` without reallocating.
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            priorities: Vec::with_capacity(n),
+            keys: Vec::with_capacity(n),
+            pos: vec![ABSENT; n],
+            limit: usize::MAX,
+            order: PhantomData,
+        }
+    }
+
+    /// Constructs a new, empty heap that accepts only the keys `0..n` and can hold all of them without reallocating.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{ErrorKind, IndexHeap, Min};
+    ///
+    /// let mut heap: IndexHeap<u32, Min, 2> = IndexHeap::with_key_limit(4);
+    /// heap.insert(3, 70).unwrap();
+    /// assert!(heap.insert(4, 5).unwrap_err().kind() == ErrorKind::InvalidIndex);
+    /// ```
+    pub fn with_key_limit(n: usize) -> Self {
+        Self {
+            limit: n,
+            ..Self::with_capacity(n)
+        }
+    }
+
+    /// Returns the number of keys the heap accepts. Valid keys are `0..key_limit()`.
+    pub fn key_limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the number of keys on the heap.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if the heap contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Removes all keys from the heap.
+    /// Note that this method has no effect on the allocated capacity of the heap.
+    pub fn clear(&mut self) {
+        for &key in self.keys.iter() {
+            self.pos[key] = ABSENT;
+        }
+        self.keys.clear();
+        self.priorities.clear();
+    }
+
+    /// Returns true if `key` is on the heap.
+    pub fn contains(&self, key: usize) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Returns the priority associated with `key`, or `None` if the key is not on the heap.
+    pub fn priority(&self, key: usize) -> Option<&P> {
+        self.position(key).map(|i| &self.priorities[i])
+    }
+
+    /// Returns the key and priority on top of the heap without removing them, or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        if self.keys.is_empty() {
+            None
+        } else {
+            Some((self.keys[0], &self.priorities[0]))
+        }
+    }
+
+    /// Inserts `key` with the given priority.
+    /// Returns an error if the key is already on the heap or is at or past the key limit.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{ErrorKind, IndexHeap, Min};
+    ///
+    /// let mut heap: IndexHeap<u32, Min, 2> = IndexHeap::with_capacity(4);
+    /// heap.insert(3, 70).unwrap();
+    /// heap.insert(1, 20).unwrap();
+    /// assert!(heap.insert(1, 5).unwrap_err().kind() == ErrorKind::DuplicateKey);
+    /// assert!(heap.insert(usize::MAX, 5).unwrap_err().kind() == ErrorKind::InvalidIndex);
+    /// assert!(heap.peek() == Some((1, &20)));
+    /// ```
+    pub fn insert(&mut self, key: usize, priority: P) -> Result<()> {
+        if key >= self.limit {
+            return Err(Error::new(
+                ErrorKind::InvalidIndex,
+                "The key is at or past the key limit of the heap.",
+            ));
+        }
+        if self.contains(key) {
+            return Err(Error::new(
+                ErrorKind::DuplicateKey,
+                "The key is already on the heap.",
+            ));
+        }
+        if key >= self.pos.len() {
+            self.pos.resize(key + 1, ABSENT);
+        }
+        let index: usize = self.keys.len();
+        self.keys.push(key);
+        self.priorities.push(priority);
+        self.pos[key] = index;
+        self.sort_up(index);
+        Ok(())
+    }
+
+    /// Replaces the priority of `key` and returns its old priority.
+    /// Returns an error if the key is not on the heap.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{IndexHeap, Max};
+    ///
+    /// let mut heap: IndexHeap<u32, Max, 4> = IndexHeap::new();
+    /// heap.insert(0, 10).unwrap();
+    /// heap.insert(1, 20).unwrap();
+    /// assert!(heap.change(0, 30).unwrap() == 10);
+    /// assert!(heap.peek() == Some((0, &30)));
+    /// ```
+    pub fn change(&mut self, key: usize, priority: P) -> Result<P> {
+        let index: usize = self.checked_position(key)?;
+        let old: P = std::mem::replace(&mut self.priorities[index], priority);
+        self.reposition(index, &old);
+        Ok(old)
+    }
+
+    /// Moves `key` toward the top of the heap by changing its priority to `priority`, like the
+    /// decrease-key operation of a minimum heap. With a [`crate::Max`] policy, this raises the value.
+    /// Returns an error if the key is not on the heap or if `O` puts `priority` behind the current priority.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{IndexHeap, Min};
+    ///
+    /// let mut heap: IndexHeap<u32, Min, 2> = IndexHeap::new();
+    /// heap.insert(0, 10).unwrap();
+    /// heap.insert(1, 20).unwrap();
+    /// heap.decrease(1, 5).unwrap();
+    /// assert!(heap.decrease(1, 8).is_err());
+    /// assert!(heap.pop() == Some((1, 5)));
+    /// ```
+    pub fn decrease(&mut self, key: usize, priority: P) -> Result<()> {
+        let index: usize = self.checked_position(key)?;
+        if O::precedes(&self.priorities[index], &priority) {
+            return Err(Error::new(
+                ErrorKind::InvalidPriority,
+                "The new priority is behind the current priority.",
+            ));
+        }
+        let old: P = std::mem::replace(&mut self.priorities[index], priority);
+        self.reposition(index, &old);
+        Ok(())
+    }
+
+    /// Moves `key` away from the top of the heap by changing its priority to `priority`, like the
+    /// increase-key operation of a minimum heap. With a [`crate::Max`] policy, this lowers the value.
+    /// Returns an error if the key is not on the heap or if `O` puts `priority` ahead of the current priority.
+    pub fn increase(&mut self, key: usize, priority: P) -> Result<()> {
+        let index: usize = self.checked_position(key)?;
+        if O::precedes(&priority, &self.priorities[index]) {
+            return Err(Error::new(
+                ErrorKind::InvalidPriority,
+                "The new priority is ahead of the current priority.",
+            ));
+        }
+        let old: P = std::mem::replace(&mut self.priorities[index], priority);
+        self.reposition(index, &old);
+        Ok(())
+    }
+
+    /// Removes `key` from the heap and returns its priority.
+    /// Returns an error if the key is not on the heap.
+    pub fn delete(&mut self, key: usize) -> Result<P> {
+        let index: usize = self.checked_position(key)?;
+        Ok(self.remove_at(index).1)
+    }
+
+    /// Removes and returns the key and priority on top of the heap. Returns `None` if the heap is empty.
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.keys.is_empty() {
+            None
+        } else {
+            Some(self.remove_at(0))
+        }
+    }
+
+    /// Returns true if the heap property holds and the position arrays agree with each other.
+    /// Please note that this function is intended for use during testing.
+    #[doc(hidden)]
+    pub fn is_valid(&self) -> bool {
+        (1..self.keys.len())
+            .all(|i| !O::precedes(&self.priorities[i], &self.priorities[(i - 1) / BRANCHES]))
+            && self.keys.iter().enumerate().all(|(i, &k)| self.pos[k] == i)
+            && self.pos.iter().filter(|&&p| p != ABSENT).count() == self.keys.len()
+    }
+
+    fn position(&self, key: usize) -> Option<usize> {
+        match self.pos.get(key) {
+            Some(&index) if index != ABSENT => Some(index),
+            _ => None,
+        }
+    }
+
+    fn checked_position(&self, key: usize) -> Result<usize> {
+        self.position(key).ok_or(Error::new(
+            ErrorKind::KeyNotFound,
+            "The key is not on the heap.",
+        ))
+    }
+
+    fn remove_at(&mut self, index: usize) -> (usize, P) {
+        let key: usize = self.keys.swap_remove(index);
+        let removed: P = self.priorities.swap_remove(index);
+        self.pos[key] = ABSENT;
+        if index < self.keys.len() {
+            self.pos[self.keys[index]] = index;
+            if O::precedes(&self.priorities[index], &removed) {
+                self.sort_up(index);
+            } else {
+                self.sort_down(index);
+            }
+        }
+        (key, removed)
+    }
+
+    /// Restores the heap property after the priority at `index` has been changed from `old`.
+    fn reposition(&mut self, index: usize, old: &P) {
+        if O::precedes(&self.priorities[index], old) {
+            self.sort_up(index);
+        } else {
+            self.sort_down(index);
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.priorities.swap(a, b);
+        self.keys.swap(a, b);
+        self.pos[self.keys[a]] = a;
+        self.pos[self.keys[b]] = b;
+    }
+
+    fn sort_down(&mut self, mut index: usize) {
+        let length: usize = self.keys.len();
+        loop {
+            let first_child: usize = (index * BRANCHES) + 1;
+            let last_child: usize = first_child + BRANCHES;
+            let mut priority: usize = index;
+            for i in first_child..last_child.min(length) {
+                if O::precedes(&self.priorities[i], &self.priorities[priority]) {
+                    priority = i;
+                }
+            }
+            if priority == index {
+                break;
+            }
+            self.swap(priority, index);
+            index = priority;
+        }
+    }
+
+    fn sort_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let p: usize = (index - 1) / BRANCHES;
+            if O::precedes(&self.priorities[index], &self.priorities[p]) {
+                self.swap(index, p);
+            } else {
+                break;
+            }
+            index = p;
+        }
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::Order;
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+/// An iterator over the elements of a heap that are strictly ahead of a bound, in no particular order.
+/// Subtrees whose root is not ahead of the bound are skipped without being visited.
+///
+/// This struct is created by [`crate::Heap::iter_better_than`].
+pub struct BetterThan<'a, T, O, const BRANCHES: usize>
+where
+    O: Order<T>,
+{
+    pub(crate) heap: &'a [T],
+    pub(crate) bound: T,
+    pub(crate) stack: Vec<usize>,
+    pub(crate) order: PhantomData<O>,
+}
+
+impl<'a, T, O, const BRANCHES: usize> Clone for BetterThan<'a, T, O, BRANCHES>
+where
+    O: Order<T>,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap,
+            bound: self.bound.clone(),
+            stack: self.stack.clone(),
+            order: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, O, const BRANCHES: usize> Debug for BetterThan<'a, T, O, BRANCHES>
+where
+    O: Order<T>,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BetterThan")
+            .field("heap", &self.heap)
+            .field("bound", &self.bound)
+            .field("stack", &self.stack)
+            .finish()
+    }
+}
+
+impl<'a, T, O, const BRANCHES: usize> Iterator for BetterThan<'a, T, O, BRANCHES>
+where
+    O: Order<T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        while let Some(index) = self.stack.pop() {
+            if O::precedes(&self.heap[index], &self.bound) {
+                let first_child: usize = (index * BRANCHES) + 1;
+                let last_child: usize = (first_child + BRANCHES).min(self.heap.len());
+                self.stack.extend((first_child..last_child).rev());
+                return Some(&self.heap[index]);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.heap.len()))
+    }
+}
+
+impl<T, O, const BRANCHES: usize> FusedIterator for BetterThan<'_, T, O, BRANCHES> where O: Order<T> {}
+
+/// An iterator over the elements of a heap in priority order that leaves the heap untouched.
+///
+/// The iterator keeps its own binary heap of indices, the frontier, holding the children of every
+/// element yielded so far. The next element in priority order is always on top of the frontier,
+/// so yielding the first k elements costs O(k log k) comparisons for a fixed number of branches.
+///
+/// This struct is created by [`crate::Heap::iter_sorted`].
+pub struct IterSorted<'a, T, O, const BRANCHES: usize>
+where
+    O: Order<T>,
+{
+    pub(crate) heap: &'a [T],
+    pub(crate) frontier: Vec<usize>,
+    pub(crate) remaining: usize,
+    pub(crate) order: PhantomData<O>,
+}
+
+impl<'a, T, O, const BRANCHES: usize> Clone for IterSorted<'a, T, O, BRANCHES>
+where
+    O: Order<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap,
+            frontier: self.frontier.clone(),
+            remaining: self.remaining,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, O, const BRANCHES: usize> Debug for IterSorted<'a, T, O, BRANCHES>
+where
+    O: Order<T>,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IterSorted")
+            .field("heap", &self.heap)
+            .field("frontier", &self.frontier)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> IterSorted<'_, T, O, BRANCHES>
+where
+    O: Order<T>,
+{
+    fn push(&mut self, index: usize) {
+        let mut i: usize = self.frontier.len();
+        self.frontier.push(index);
+        while i > 0 {
+            let p: usize = (i - 1) / 2;
+            if O::precedes(&self.heap[self.frontier[i]], &self.heap[self.frontier[p]]) {
+                self.frontier.swap(i, p);
+                i = p;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        let len: usize = self.frontier.len();
+        if len == 0 {
+            return None;
+        }
+        let index: usize = self.frontier.swap_remove(0);
+        let len: usize = len - 1;
+        let mut i: usize = 0;
+        loop {
+            let mut priority: usize = i;
+            for c in ((i * 2) + 1)..((i * 2) + 3).min(len) {
+                if O::precedes(
+                    &self.heap[self.frontier[c]],
+                    &self.heap[self.frontier[priority]],
+                ) {
+                    priority = c;
+                }
+            }
+            if priority == i {
+                break;
+            }
+            self.frontier.swap(i, priority);
+            i = priority;
+        }
+        Some(index)
+    }
+}
+
+impl<'a, T, O, const BRANCHES: usize> Iterator for IterSorted<'a, T, O, BRANCHES>
+where
+    O: Order<T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let index: usize = self.pop()?;
+        let first_child: usize = (index * BRANCHES) + 1;
+        let last_child: usize = (first_child + BRANCHES).min(self.heap.len());
+        for child in first_child..last_child {
+            self.push(child);
+        }
+        self.remaining -= 1;
+        Some(&self.heap[index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, O, const BRANCHES: usize> ExactSizeIterator for IterSorted<'_, T, O, BRANCHES> where
+    O: Order<T>
+{
+}
+
+impl<T, O, const BRANCHES: usize> FusedIterator for IterSorted<'_, T, O, BRANCHES> where O: Order<T> {}
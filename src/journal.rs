@@ -0,0 +1,470 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Error, ErrorKind, Heap, Order, PriorityQueue, Result};
+use std::fmt::{Debug, Display};
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// One mutating call made on a [`JournaledHeap`], along with its arguments and result.
+///
+/// Each entry is written to a text journal as a single line of whitespace separated fields,
+/// with `-` standing for a missing result, so the `Display` output of `T` must not contain whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalEntry<T> {
+    /// `element` was inserted and ended up at `index`.
+    Insert { element: T, index: usize },
+    /// The element on top was removed, or `None` if the heap was empty.
+    Top { result: Option<T> },
+    /// The element at `index` was removed, or `None` if the call returned an error.
+    Remove { index: usize, result: Option<T> },
+    /// The element at `index` was given a new value and moved to a new index, or `None` if the call returned an error.
+    Update {
+        index: usize,
+        result: Option<(T, usize)>,
+    },
+    /// The heap was cleared.
+    Clear,
+}
+
+impl<T: Display> Display for JournalEntry<T> {
+    /// Formats the entry as one line of a text journal.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JournalEntry::Insert { element, index } => write!(f, "insert {} {}", element, index),
+            JournalEntry::Top { result: Some(x) } => write!(f, "top {}", x),
+            JournalEntry::Top { result: None } => f.write_str("top -"),
+            JournalEntry::Remove {
+                index,
+                result: Some(x),
+            } => write!(f, "remove {} {}", index, x),
+            JournalEntry::Remove {
+                index,
+                result: None,
+            } => write!(f, "remove {} -", index),
+            JournalEntry::Update {
+                index,
+                result: Some((x, new_index)),
+            } => write!(f, "update {} {} {}", index, x, new_index),
+            JournalEntry::Update {
+                index,
+                result: None,
+            } => write!(f, "update {} -", index),
+            JournalEntry::Clear => f.write_str("clear"),
+        }
+    }
+}
+
+impl<T: FromStr> FromStr for JournalEntry<T> {
+    type Err = Error;
+
+    /// Parses one line of a text journal.
+    fn from_str(s: &str) -> Result<Self> {
+        fn field<F: FromStr>(field: Option<&str>) -> Result<F> {
+            field.and_then(|x| x.parse().ok()).ok_or(Error::new(
+                ErrorKind::InvalidJournal,
+                "Malformed journal entry.",
+            ))
+        }
+        let mut fields = s.split_whitespace();
+        let entry: JournalEntry<T> = match fields.next() {
+            Some("insert") => JournalEntry::Insert {
+                element: field(fields.next())?,
+                index: field(fields.next())?,
+            },
+            Some("top") => match fields.next() {
+                Some("-") => JournalEntry::Top { result: None },
+                x => JournalEntry::Top {
+                    result: Some(field(x)?),
+                },
+            },
+            Some("remove") => JournalEntry::Remove {
+                index: field(fields.next())?,
+                result: match fields.next() {
+                    Some("-") => None,
+                    x => Some(field(x)?),
+                },
+            },
+            Some("update") => JournalEntry::Update {
+                index: field(fields.next())?,
+                result: match fields.next() {
+                    Some("-") => None,
+                    x => Some((field(x)?, field(fields.next())?)),
+                },
+            },
+            Some("clear") => JournalEntry::Clear,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidJournal,
+                    "Unknown journal entry.",
+                ))
+            }
+        };
+        match fields.next() {
+            None => Ok(entry),
+            Some(_) => Err(Error::new(
+                ErrorKind::InvalidJournal,
+                "Malformed journal entry.",
+            )),
+        }
+    }
+}
+
+/// An error found at a particular entry of a journal, either while reading it or while replaying it.
+#[derive(Debug, Copy, Clone)]
+pub struct JournalError {
+    step: usize,
+    line: Option<usize>,
+    error: Error,
+}
+
+impl JournalError {
+    /// Returns the zero-based position of the offending entry in the journal.
+    /// Blank lines skipped by [`read_journal`] are not counted.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Returns the one-based line of the text journal that could not be read,
+    /// or `None` if the error was found while replaying.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Returns the underlying error.
+    pub fn error(&self) -> Error {
+        self.error
+    }
+}
+
+impl Display for JournalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "Line {}: {}", line, self.error),
+            None => write!(f, "Step {}: {}", self.step, self.error),
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+/// A destination for the entries recorded by a [`JournaledHeap`].
+pub trait JournalSink<T> {
+    /// Records one entry.
+    fn record(&mut self, entry: &JournalEntry<T>);
+}
+
+/// Keeps the journal in memory.
+impl<T: Copy> JournalSink<T> for Vec<JournalEntry<T>> {
+    fn record(&mut self, entry: &JournalEntry<T>) {
+        self.push(*entry);
+    }
+}
+
+/// Writes the journal as text, one entry per line, to a file or any other writer.
+///
+/// Recording can not fail, so the first I/O error is kept and every entry after it is dropped.
+/// Check [`FileSink::error`] or call [`FileSink::flush`] once the journal is complete.
+#[derive(Debug)]
+pub struct FileSink<W: Write> {
+    writer: W,
+    error: Option<std::io::Error>,
+}
+
+impl FileSink<BufWriter<File>> {
+    /// Creates a file at `path`, truncating it if it exists, and writes the journal to it through a buffer.
+    pub fn create<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> FileSink<W> {
+    /// Writes the journal to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Returns the first I/O error that occurred while recording, if any.
+    pub fn error(&self) -> Option<&std::io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Flushes the writer. Returns the first I/O error that occurred while recording, if any.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => self.writer.flush(),
+        }
+    }
+
+    /// Consumes the sink and returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<T: Display, W: Write> JournalSink<T> for FileSink<W> {
+    fn record(&mut self, entry: &JournalEntry<T>) {
+        if self.error.is_none() {
+            if let Err(e) = writeln!(self.writer, "{}", entry) {
+                self.error = Some(e);
+            }
+        }
+    }
+}
+
+/// Reads a text journal written by a [`FileSink`]. Blank lines are skipped.
+pub fn read_journal<T, R>(reader: R) -> std::result::Result<Vec<JournalEntry<T>>, JournalError>
+where
+    T: FromStr,
+    R: BufRead,
+{
+    let mut journal: Vec<JournalEntry<T>> = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let step: usize = journal.len();
+        let line: String = line.map_err(|_| JournalError {
+            step,
+            line: Some(number + 1),
+            error: Error::new(ErrorKind::InvalidJournal, "Could not read the journal."),
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        journal.push(line.parse().map_err(|error| JournalError {
+            step,
+            line: Some(number + 1),
+            error,
+        })?);
+    }
+    Ok(journal)
+}
+
+/// Rebuilds a heap by replaying a journal from an empty heap, checking every call against its recorded result.
+/// Returns the first entry whose result differs from the recorded one.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{replay, Heap, JournalEntry, JournaledHeap, Min};
+///
+/// let mut heap: JournaledHeap<u32, Min, 2, Vec<JournalEntry<u32>>> = JournaledHeap::new(Vec::new());
+/// heap.insert(8);
+/// heap.insert(3);
+/// heap.update(1, |x| *x = 9).unwrap();
+/// heap.top();
+///
+/// let rebuilt: Heap<u32, Min, 2> = replay(heap.sink()).unwrap();
+/// assert!(rebuilt.as_slice() == heap.as_heap().as_slice());
+/// ```
+pub fn replay<T, O, const BRANCHES: usize>(
+    journal: &[JournalEntry<T>],
+) -> std::result::Result<Heap<T, O, BRANCHES>, JournalError>
+where
+    T: Copy + PartialEq,
+    O: Order<T>,
+{
+    let mut heap: Heap<T, O, BRANCHES> = Heap::new();
+    for (step, entry) in journal.iter().enumerate() {
+        let matches: bool = match *entry {
+            JournalEntry::Insert { element, index } => {
+                heap.insert_hooked(element, &mut ()) == index
+            }
+            JournalEntry::Top { result } => heap.top() == result,
+            JournalEntry::Remove { index, result } => heap.remove(index).ok() == result,
+            JournalEntry::Update { index, result } => match result {
+                Some((value, new_index)) => heap
+                    .update_with(index, |x| *x = value)
+                    .is_ok_and(|(i, _)| i == new_index),
+                None => heap.check_index(index).is_err(),
+            },
+            JournalEntry::Clear => {
+                heap.clear();
+                true
+            }
+        };
+        if !matches {
+            return Err(JournalError {
+                step,
+                line: None,
+                error: Error::new(
+                    ErrorKind::ReplayMismatch,
+                    "The replayed call did not return the recorded result.",
+                ),
+            });
+        }
+    }
+    Ok(heap)
+}
+
+/// A heap that records every mutating call, with its arguments and result, to a [`JournalSink`].
+///
+/// The journal starts from an empty heap, so [`replay`] can rebuild the exact same heap from it.
+/// Updates are recorded by the value the modifier produced rather than by the modifier itself.
+pub struct JournaledHeap<T, O, const BRANCHES: usize, S>
+where
+    T: Copy,
+    O: Order<T>,
+    S: JournalSink<T>,
+{
+    heap: Heap<T, O, BRANCHES>,
+    sink: S,
+}
+
+impl<T, O, const BRANCHES: usize, S> Clone for JournaledHeap<T, O, BRANCHES, S>
+where
+    T: Copy,
+    O: Order<T>,
+    S: JournalSink<T> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            sink: self.sink.clone(),
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize, S> Debug for JournaledHeap<T, O, BRANCHES, S>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+    S: JournalSink<T> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JournaledHeap")
+            .field("heap", &self.heap)
+            .field("sink", &self.sink)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize, S> JournaledHeap<T, O, BRANCHES, S>
+where
+    T: Copy,
+    O: Order<T>,
+    S: JournalSink<T>,
+{
+    /// Constructs a new, empty heap that records to `sink`.
+    pub fn new(sink: S) -> Self {
+        Self {
+            heap: Heap::new(),
+            sink,
+        }
+    }
+
+    /// Returns the underlying heap.
+    pub fn as_heap(&self) -> &Heap<T, O, BRANCHES> {
+        &self.heap
+    }
+
+    /// Returns the sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Returns a mutable reference to the sink.
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consumes the wrapper and returns the heap and the sink.
+    pub fn into_parts(self) -> (Heap<T, O, BRANCHES>, S) {
+        (self.heap, self.sink)
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns an immutable reference to the element on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Inserts an element into the heap and returns its index.
+    pub fn insert(&mut self, element: T) -> usize {
+        let index: usize = self.heap.insert_hooked(element, &mut ());
+        self.sink.record(&JournalEntry::Insert { element, index });
+        index
+    }
+
+    /// Removes and returns the element from the top of the heap. Returns `None` if the heap is empty.
+    pub fn top(&mut self) -> Option<T> {
+        let result: Option<T> = self.heap.top();
+        self.sink.record(&JournalEntry::Top { result });
+        result
+    }
+
+    /// Removes and returns the element at `index`.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<T> {
+        let result: Result<T> = self.heap.remove(index);
+        self.sink.record(&JournalEntry::Remove {
+            index,
+            result: result.ok(),
+        });
+        result
+    }
+
+    /// Updates the value of the element at `index` and returns its new index.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn update<F>(&mut self, index: usize, modifier: F) -> Result<usize>
+    where
+        F: FnOnce(&mut T),
+    {
+        let result: Result<(usize, T)> = self.heap.update_with(index, |x| {
+            modifier(x);
+            *x
+        });
+        self.sink.record(&JournalEntry::Update {
+            index,
+            result: result.ok().map(|(i, x)| (x, i)),
+        });
+        result.map(|(i, _)| i)
+    }
+
+    /// Clears the heap, removing all elements.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.sink.record(&JournalEntry::Clear);
+    }
+}
+
+impl<T, O, const BRANCHES: usize, S> PriorityQueue<T> for JournaledHeap<T, O, BRANCHES, S>
+where
+    T: Copy,
+    O: Order<T>,
+    S: JournalSink<T>,
+{
+    fn push(&mut self, element: T) {
+        self.insert(element);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        JournaledHeap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.top()
+    }
+
+    fn len(&self) -> usize {
+        JournaledHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        JournaledHeap::clear(self)
+    }
+}
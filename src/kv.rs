@@ -0,0 +1,329 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Error, ErrorKind, Order, PriorityQueue, Result};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// A heap of key/value pairs that is ordered by key only.
+///
+/// The value is carried along untouched, so it does not need to implement `Ord`, `Eq` or `Copy`.
+/// This removes the need to wrap payloads in structs with hand-written `Ord` implementations
+/// that ignore the payload.
+pub struct KVHeap<K, V, O, const BRANCHES: usize>
+where
+    O: Order<K>,
+{
+    heap: Vec<(K, V)>,
+    order: PhantomData<O>,
+}
+
+impl<K, V, O, const BRANCHES: usize> Clone for KVHeap<K, V, O, BRANCHES>
+where
+    O: Order<K>,
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            order: PhantomData,
+        }
+    }
+}
+
+impl<K, V, O, const BRANCHES: usize> Debug for KVHeap<K, V, O, BRANCHES>
+where
+    O: Order<K>,
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KVHeap").field("heap", &self.heap).finish()
+    }
+}
+
+impl<K, V, O, const BRANCHES: usize> Default for KVHeap<K, V, O, BRANCHES>
+where
+    O: Order<K>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, O, const BRANCHES: usize> From<Vec<(K, V)>> for KVHeap<K, V, O, BRANCHES>
+where
+    O: Order<K>,
+{
+    /// Builds a new heap from a vector of key/value pairs, reusing the vector's allocation.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{KVHeap, Min};
+    ///
+    /// let v: Vec<(u32, String)> = vec![(3, "c".to_string()), (1, "a".to_string()), (2, "b".to_string())];
+    /// let mut heap: KVHeap<u32, String, Min, 2> = KVHeap::from(v);
+    /// assert!(heap.pop() == Some((1, "a".to_string())));
+    /// ```
+    fn from(mut heap: Vec<(K, V)>) -> Self {
+        let len: usize = heap.len();
+        if len > 1 {
+            for index in (0..=(len - 2) / BRANCHES).rev() {
+                Self::sort_down(&mut heap, index);
+            }
+        }
+        Self {
+            heap,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<K, V, O, const BRANCHES: usize> KVHeap<K, V, O, BRANCHES>
+where
+    O: Order<K>,
+{
+    /// Constructs a new, empty heap.
+    /// The new heap will allocate memory as elements are inserted.
+    pub fn new() -> Self {
+        Self {
+            heap: Vec::new(),
+            order: PhantomData,
+        }
+    }
+
+    /// Constructs a new, empty heap with at least the specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            heap: Vec::with_capacity(capacity),
+            order: PhantomData,
+        }
+    }
+
+    /// Returns the number of key/value pairs in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the heap contains no key/value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Clears the heap, removing all key/value pairs.
+    /// Note that this method has no effect on the allocated capacity of the heap.
+    pub fn clear(&mut self) {
+        self.heap.clear()
+    }
+
+    /// Returns a slice containing the entire underlying vector.
+    pub fn as_slice(&self) -> &[(K, V)] {
+        self.heap.as_slice()
+    }
+
+    /// Returns an iterator over the key/value pairs in the order they are stored in the underlying vector.
+    pub fn iter(&self) -> std::slice::Iter<'_, (K, V)> {
+        self.heap.iter()
+    }
+
+    /// Returns the key/value pair on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.heap.first().map(|(k, v)| (k, v))
+    }
+
+    /// Returns the key/value pair at `index`, or `None` if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<(&K, &V)> {
+        self.heap.get(index).map(|(k, v)| (k, v))
+    }
+
+    /// Returns a mutable reference to the value at `index`, or `None` if the index is out of bounds.
+    /// Values do not take part in the ordering, so they can be modified freely.
+    pub fn value_mut(&mut self, index: usize) -> Option<&mut V> {
+        self.heap.get_mut(index).map(|(_, v)| v)
+    }
+
+    /// Performs a linear search (in O(n) time) to find the index of the first pair whose key is equal to `key`.
+    /// Returns `None` if the key was not found.
+    pub fn find_key(&self, key: &K) -> Option<usize>
+    where
+        K: PartialEq,
+    {
+        self.heap.iter().position(|(k, _)| k == key)
+    }
+
+    /// Inserts a key/value pair into the heap.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{KVHeap, Max};
+    ///
+    /// struct Job {
+    ///     name: &'static str,
+    /// }
+    ///
+    /// let mut heap: KVHeap<u32, Job, Max, 4> = KVHeap::new();
+    /// heap.insert(2, Job { name: "build" });
+    /// heap.insert(9, Job { name: "deploy" });
+    /// assert!(heap.peek().map(|(k, job)| (*k, job.name)) == Some((9, "deploy")));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) {
+        let index: usize = self.heap.len();
+        self.heap.push((key, value));
+        Self::sort_up(&mut self.heap, index);
+    }
+
+    /// Removes and returns the key/value pair from the top of the heap. Returns `None` if the heap is empty.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.heap.is_empty() {
+            None
+        } else {
+            let removed: (K, V) = self.heap.swap_remove(0);
+            Self::sort_down(&mut self.heap, 0);
+            Some(removed)
+        }
+    }
+
+    /// Removes and returns the key/value pair at `index`.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<(K, V)> {
+        self.check_index(index)?;
+        let removed: (K, V) = self.heap.swap_remove(index);
+        if index < self.heap.len() {
+            if O::precedes(&self.heap[index].0, &removed.0) {
+                Self::sort_up(&mut self.heap, index);
+            } else {
+                Self::sort_down(&mut self.heap, index);
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Updates the key (or "priority") of the pair at `index` and restores the heap property.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{KVHeap, Min};
+    ///
+    /// let mut heap: KVHeap<u32, Vec<u8>, Min, 2> = KVHeap::new();
+    /// heap.insert(5, vec![5]);
+    /// heap.insert(7, vec![7]);
+    /// let index: usize = heap.find_key(&7).unwrap();
+    /// heap.update_key(index, |k| *k = 1).unwrap();
+    /// assert!(heap.pop() == Some((1, vec![7])));
+    /// ```
+    pub fn update_key<F>(&mut self, index: usize, modifier: F) -> Result<()>
+    where
+        F: FnOnce(&mut K),
+    {
+        self.check_index(index)?;
+        modifier(&mut self.heap[index].0);
+        self.restore(index);
+        Ok(())
+    }
+
+    /// Updates the value of the pair at `index`. The heap's ordering is not affected.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn update_value<F>(&mut self, index: usize, modifier: F) -> Result<()>
+    where
+        F: FnOnce(&mut V),
+    {
+        self.check_index(index)?;
+        modifier(&mut self.heap[index].1);
+        Ok(())
+    }
+
+    /// Returns true if the heap property holds for every pair.
+    /// Please note that this function is intended for use during testing.
+    #[doc(hidden)]
+    pub fn is_valid(&self) -> bool {
+        (1..self.heap.len())
+            .all(|i| !O::precedes(&self.heap[i].0, &self.heap[(i - 1) / BRANCHES].0))
+    }
+
+    fn check_index(&self, index: usize) -> Result<()> {
+        if self.heap.is_empty() {
+            Err(Error::new(
+                ErrorKind::EmptyHeap,
+                "Can not access elements of an empty heap.",
+            ))
+        } else if index >= self.heap.len() {
+            Err(Error::new(
+                ErrorKind::InvalidIndex,
+                "Index is beyond the end of the heap.",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn restore(&mut self, index: usize) -> usize {
+        if index == 0 || !O::precedes(&self.heap[index].0, &self.heap[(index - 1) / BRANCHES].0) {
+            Self::sort_down(&mut self.heap, index)
+        } else {
+            Self::sort_up(&mut self.heap, index)
+        }
+    }
+
+    fn sort_down(heap: &mut [(K, V)], mut index: usize) -> usize {
+        let length: usize = heap.len();
+        loop {
+            let first_child: usize = (index * BRANCHES) + 1;
+            let last_child: usize = first_child + BRANCHES;
+            let mut priority: usize = index;
+            for i in first_child..last_child.min(length) {
+                if O::precedes(&heap[i].0, &heap[priority].0) {
+                    priority = i;
+                }
+            }
+            if priority == index {
+                return index;
+            }
+            heap.swap(priority, index);
+            index = priority;
+        }
+    }
+
+    fn sort_up(heap: &mut [(K, V)], mut index: usize) -> usize {
+        while index > 0 {
+            let p: usize = (index - 1) / BRANCHES;
+            if O::precedes(&heap[index].0, &heap[p].0) {
+                heap.swap(index, p);
+            } else {
+                break;
+            }
+            index = p;
+        }
+        index
+    }
+}
+
+impl<K, V, O, const BRANCHES: usize> PriorityQueue<(K, V)> for KVHeap<K, V, O, BRANCHES>
+where
+    O: Order<K>,
+{
+    fn push(&mut self, element: (K, V)) {
+        self.insert(element.0, element.1)
+    }
+
+    fn peek(&self) -> Option<&(K, V)> {
+        self.heap.first()
+    }
+
+    fn pop(&mut self) -> Option<(K, V)> {
+        KVHeap::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        KVHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        KVHeap::clear(self)
+    }
+}
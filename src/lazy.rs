@@ -0,0 +1,282 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Heap, Order};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// The fraction of dead entries at which a [`LazyHeap`] compacts itself, unless another threshold is given.
+pub const DEFAULT_COMPACTION_THRESHOLD: f64 = 0.5;
+
+/// An element together with the ID it was given on insertion.
+#[derive(Debug, Clone, Copy)]
+struct Entry<T> {
+    value: T,
+    id: usize,
+}
+
+/// Orders entries by their values alone.
+#[derive(Debug, Clone)]
+struct ByValue<O>(PhantomData<O>);
+
+impl<T, O> Order<Entry<T>> for ByValue<O>
+where
+    O: Order<T>,
+{
+    #[inline]
+    fn compare(a: &Entry<T>, b: &Entry<T>) -> Ordering {
+        O::compare(&a.value, &b.value)
+    }
+}
+
+/// A snapshot of the live and dead entries of a [`LazyHeap`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LazyStats {
+    /// The number of entries that have not been cancelled.
+    pub live: usize,
+    /// The number of cancelled entries that are still stored on the heap.
+    pub dead: usize,
+    /// The number of times the heap has been compacted.
+    pub compactions: usize,
+}
+
+/// A heap that cancels elements by marking them dead instead of searching for them.
+///
+/// Every inserted element is given an ID. `cancel(id)` records a tombstone in O(1) expected time,
+/// and `top` and `peek` discard dead entries as they reach the top of the heap. When the fraction
+/// of dead entries reaches the compaction threshold, they are all dropped with a single `heap_sort` rebuild.
+/// Because `peek` takes `&mut self`, the heap does not implement [`crate::PriorityQueue`].
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{LazyHeap, Min};
+///
+/// let mut heap: LazyHeap<u32, Min, 4> = LazyHeap::new();
+/// let a: usize = heap.insert(10);
+/// let b: usize = heap.insert(20);
+/// heap.insert(30);
+/// assert!(heap.cancel(a));
+/// assert!(!heap.cancel(a));
+/// assert!(heap.len() == 2);
+/// assert!(heap.peek() == Some(&20));
+/// heap.cancel(b);
+/// assert!(heap.top() == Some(30));
+/// ```
+pub struct LazyHeap<T, O, const BRANCHES: usize>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    heap: Heap<Entry<T>, ByValue<O>, BRANCHES>,
+    live: HashSet<usize>,
+    dead: HashSet<usize>,
+    next_id: usize,
+    threshold: f64,
+    compactions: usize,
+}
+
+impl<T, O, const BRANCHES: usize> Clone for LazyHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            live: self.live.clone(),
+            dead: self.dead.clone(),
+            next_id: self.next_id,
+            threshold: self.threshold,
+            compactions: self.compactions,
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Debug for LazyHeap<T, O, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyHeap")
+            .field("heap", &self.heap)
+            .field("live", &self.live)
+            .field("dead", &self.dead)
+            .field("next_id", &self.next_id)
+            .field("threshold", &self.threshold)
+            .field("compactions", &self.compactions)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Default for LazyHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> LazyHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    /// Constructs a new, empty heap that compacts itself once half of its entries are dead.
+    pub fn new() -> Self {
+        Self::with_threshold(DEFAULT_COMPACTION_THRESHOLD)
+    }
+
+    /// Constructs a new, empty heap that compacts itself when the fraction of dead entries reaches `threshold`.
+    /// The threshold is clamped to the range `0.0..=1.0`. A threshold of zero compacts on every cancellation
+    /// and a threshold of one only compacts once every entry is dead.
+    pub fn with_threshold(threshold: f64) -> Self {
+        Self {
+            heap: Heap::new(),
+            live: HashSet::new(),
+            dead: HashSet::new(),
+            next_id: 0,
+            threshold: Self::clamp(threshold),
+            compactions: 0,
+        }
+    }
+
+    /// Returns the compaction threshold.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Sets the compaction threshold, clamped to the range `0.0..=1.0`, and compacts the heap if it has already been reached.
+    pub fn set_threshold(&mut self, threshold: f64) {
+        self.threshold = Self::clamp(threshold);
+        self.compact_if_needed();
+    }
+
+    /// Returns the number of live elements on the heap.
+    pub fn len(&self) -> usize {
+        self.live.len()
+    }
+
+    /// Returns true if the heap contains no live elements.
+    pub fn is_empty(&self) -> bool {
+        self.live.is_empty()
+    }
+
+    /// Returns the number of live and dead entries and how many times the heap has been compacted.
+    pub fn stats(&self) -> LazyStats {
+        LazyStats {
+            live: self.live.len(),
+            dead: self.dead.len(),
+            compactions: self.compactions,
+        }
+    }
+
+    /// Clears the heap, removing all live and dead entries.
+    /// IDs are not reused, so IDs handed out before the call stay invalid.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.live.clear();
+        self.dead.clear();
+    }
+
+    /// Returns true if the element with the given ID is on the heap and has not been cancelled.
+    pub fn contains(&self, id: usize) -> bool {
+        self.live.contains(&id)
+    }
+
+    /// Inserts an element into the heap and returns its ID.
+    pub fn insert(&mut self, element: T) -> usize {
+        let id: usize = self.next_id;
+        self.next_id += 1;
+        self.heap.insert(Entry { value: element, id });
+        self.live.insert(id);
+        id
+    }
+
+    /// Marks the element with the given ID as dead. It stays in storage until it reaches the top
+    /// of the heap or the heap is compacted. Returns false if the ID is not live.
+    pub fn cancel(&mut self, id: usize) -> bool {
+        if !self.live.remove(&id) {
+            return false;
+        }
+        self.dead.insert(id);
+        self.compact_if_needed();
+        true
+    }
+
+    /// Discards any dead entries on top of the heap and returns a reference to the live element on top.
+    /// Returns `None` if the heap has no live elements.
+    pub fn peek(&mut self) -> Option<&T> {
+        self.discard_dead();
+        self.heap.peek().map(|e| &e.value)
+    }
+
+    /// Discards any dead entries on top of the heap and returns the ID of the live element on top along with a reference to it.
+    pub fn peek_with_id(&mut self) -> Option<(usize, &T)> {
+        self.discard_dead();
+        self.heap.peek().map(|e| (e.id, &e.value))
+    }
+
+    /// Removes and returns the live element on top of the heap, discarding any dead entries above it.
+    /// Returns `None` if the heap has no live elements.
+    pub fn top(&mut self) -> Option<T> {
+        self.discard_dead();
+        let entry: Entry<T> = self.heap.top()?;
+        self.live.remove(&entry.id);
+        Some(entry.value)
+    }
+
+    /// Drops every dead entry with a single `heap_sort` rebuild.
+    pub fn compact(&mut self) {
+        if self.dead.is_empty() {
+            return;
+        }
+        let dead: &HashSet<usize> = &self.dead;
+        self.heap.retain(|e| !dead.contains(&e.id));
+        self.dead.clear();
+        self.compactions += 1;
+    }
+
+    /// Returns true if the heap property holds and the live and dead sets agree with the stored entries.
+    /// Please note that this function is intended for use during testing.
+    #[doc(hidden)]
+    pub fn is_valid(&self) -> bool {
+        self.heap.is_valid()
+            && self.heap.len() == self.live.len() + self.dead.len()
+            && self
+                .heap
+                .iter()
+                .all(|e| self.live.contains(&e.id) != self.dead.contains(&e.id))
+    }
+
+    fn discard_dead(&mut self) {
+        while let Some(entry) = self.heap.peek() {
+            if self.dead.remove(&entry.id) {
+                self.heap.top();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn compact_if_needed(&mut self) {
+        let stored: usize = self.heap.len();
+        if !self.dead.is_empty() && self.dead.len() as f64 >= self.threshold * stored as f64 {
+            self.compact();
+        }
+    }
+
+    fn clamp(threshold: f64) -> f64 {
+        if threshold.is_nan() {
+            DEFAULT_COMPACTION_THRESHOLD
+        } else {
+            threshold.clamp(0.0, 1.0)
+        }
+    }
+}
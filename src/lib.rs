@@ -9,22 +9,102 @@
  * 
  * - Maximum heaps
  * - Minimum heaps, without relying on [`core::cmp::Reverse`] or a custom [`std::cmp::Ord`] implementation
+ * - Type-level ordering policies via the [`Order`] trait, such as ordering by a field or by a total order over floats
+ * - Converting between ordering policies and numbers of branches in place with `into_reversed`, `into_arity` and `convert`
+ * - Float priorities with an explicit NaN policy (first, last or rejected) via `FloatMin`, `FloatMax` and [`FloatHeap`]
+ * - Consuming and filtering the heap with `drain`, `drain_sorted`, `drain_while`, `retain` and `retain_mut`
+ * - Non-destructive iteration in priority order with `iter_sorted` and `top_k_ref`
+ * - Graphviz DOT export with highlighted nodes via `to_dot`, and an ASCII tree `Display` that can be limited in depth with `display_depth`
+ * - Navigating the d-ary tree with `parent`, `children`, `depth`, `height`, `levels`, `iter_pre_order` and a [`Cursor`]
  * - Binary and d-way heaps. Any number of branches up to (usize::MAX - 1) / d are allowed, so use good judgement!
+ * - Mergeable heaps via [`RandomizedMeldableHeap`], with expected O(log n) `meld` that copies no nodes and handle-based removal
+ * - Generic algorithms over the heaps in the crate via the [`PriorityQueue`] trait, and over stable handles via [`AddressablePriorityQueue`]
+ * - Indexed priority queues over dense `usize` keys via [`IndexHeap`], for graph algorithms such as Dijkstra's
+ * - Key/value heaps via [`KVHeap`], which order by key and carry a payload that needs no `Ord`, `Eq` or `Copy`
+ * - Duplicate-free heaps via [`SetHeap`] and multiset heaps via [`CountingHeap`], which store each value once with a count
+ * - Lazy deletion via [`LazyHeap`], which cancels elements by ID with tombstones and compacts itself past a threshold
+ * - Checkpoints with nested rollback and commit via [`TransactionalHeap`], backed by an undo log of element moves
+ * - Opt-in journaling of every mutating call via [`JournaledHeap`], with deterministic [`replay`] for offline debugging
+ * - Observer callbacks for every insertion, move and removal via [`HeapObserver`] and [`ObservedHeap`]
+ * - Counting comparisons, swaps and levels traversed per operation type via [`InstrumentedHeap`], to compare arities on real traffic
+ * - Stable heaps via [`StableHeap`], which break ties in FIFO, LIFO or seeded random order without a sequence field in `T`
  *  
  * Use the [`Heap::update`] method to modify the value of an element on the heap in such
  * a way that the element's ordering relative to other elements is changed. Modifying 
  * an element's value through other means may result in a inconsistencies, logic errors,
  * panics, or other unintended consequences.
+ *
+ * ## Breaking changes
+ *
+ * The `MAX_HEAP: bool` const parameter has been replaced by an ordering policy type `O: Order<T>`:
+ *
+ * - Write `Heap<T, Min, 2>` instead of `Heap<T, false, 2>` and `Heap<T, Max, 2>` instead of `Heap<T, true, 2>`. The [`BinaryMinHeap`], [`BinaryMaxHeap`] and other aliases keep their names.
+ * - `sort_order()` has been removed. Compare elements with [`Order::compare`] or [`Order::precedes`] instead.
+ * - [`Heap::sort_up`] and [`Heap::sort_down`] no longer take an `Ordering` argument. The policy `O` supplies the comparison.
 */
 
-use std::cmp::{Ord, Ordering};
-use std::fmt::Display;
+mod drain;
+mod float;
+mod guard;
+mod hook;
+mod index;
+mod iter;
+mod journal;
+mod kv;
+mod lazy;
+mod meldable;
+mod observer;
+mod order;
+mod queue;
+mod render;
+mod set;
+mod stable;
+mod stats;
+mod transaction;
+mod tree;
+
+pub use drain::{DrainSorted, DrainWhile, PopEqual};
+pub use float::{
+    Float, FloatHeap, FloatMax, FloatMin, FloatOrder, NanFirst, NanLast, NanPlacement, NanPolicy,
+    NanReject,
+};
+pub use guard::SliceMut;
+pub use index::IndexHeap;
+pub use iter::{BetterThan, IterSorted};
+pub use journal::{
+    read_journal, replay, FileSink, JournalEntry, JournalError, JournalSink, JournaledHeap,
+};
+pub use kv::KVHeap;
+pub use lazy::{LazyHeap, LazyStats, DEFAULT_COMPACTION_THRESHOLD};
+pub use meldable::{MeldableHandle, RandomizedMeldableHeap};
+pub use observer::{HeapObserver, ObservedHeap};
+pub use order::{Max, Min, Order, ReverseOrder};
+pub use queue::{AddressablePriorityQueue, PriorityQueue};
+pub use render::DisplayDepth;
+pub use set::{CountingHeap, SetHeap};
+pub use stable::{Fifo, Lifo, Random, StableHeap, TieBreak};
+pub use stats::{HeapStats, InstrumentedHeap, OpStats};
+pub use transaction::{Checkpoint, TransactionalHeap};
+pub use tree::{Cursor, Levels, PreOrder};
+
+use hook::SiftHook;
+use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
 
 /// An enum containing the types of errors that a heap might encounter.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ErrorKind {
     InvalidIndex,
     EmptyHeap,
+    InvalidHandle,
+    KeyNotFound,
+    DuplicateKey,
+    InvalidPriority,
+    NotANumber,
+    NotFound,
+    InvalidCheckpoint,
+    InvalidJournal,
+    ReplayMismatch,
 }
 
 impl Display for ErrorKind {
@@ -33,6 +113,15 @@ impl Display for ErrorKind {
         match *self {
             ErrorKind::InvalidIndex => f.write_str("Index out of bounds."),
             ErrorKind::EmptyHeap => f.write_str("Heap is empty."),
+            ErrorKind::InvalidHandle => f.write_str("Invalid handle."),
+            ErrorKind::KeyNotFound => f.write_str("Key not found."),
+            ErrorKind::DuplicateKey => f.write_str("Duplicate key."),
+            ErrorKind::InvalidPriority => f.write_str("Invalid priority."),
+            ErrorKind::NotANumber => f.write_str("Value is not a number."),
+            ErrorKind::NotFound => f.write_str("Element not found."),
+            ErrorKind::InvalidCheckpoint => f.write_str("Invalid checkpoint."),
+            ErrorKind::InvalidJournal => f.write_str("Invalid journal."),
+            ErrorKind::ReplayMismatch => f.write_str("Replay mismatch."),
         }
     }
 }
@@ -56,6 +145,16 @@ impl Error {
     pub fn new(kind: ErrorKind, message: &'static str) -> Self {
         Error { kind, message }
     }
+
+    /// Returns the ErrorKind of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the message string of this error.
+    pub fn message(&self) -> &'static str {
+        self.message
+    }
 }
 
 impl std::error::Error for Error {}
@@ -64,92 +163,122 @@ impl std::error::Error for Error {}
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// A minimum heap with branching factor of 2.
-pub type BinaryMinHeap<T> = Heap<T, false, 2>;
+pub type BinaryMinHeap<T> = Heap<T, Min, 2>;
 
 /// A maximum heap with branching factor of 2.
-pub type BinaryMaxHeap<T> = Heap<T, true, 2>;
+pub type BinaryMaxHeap<T> = Heap<T, Max, 2>;
 
 /// A minimum heap with branching factor of 3.
-pub type TernaryMinHeap<T> = Heap<T, false, 3>;
+pub type TernaryMinHeap<T> = Heap<T, Min, 3>;
 
 /// A maximum heap with branching factor of 3.
-pub type TernaryMaxHeap<T> = Heap<T, true, 3>;
+pub type TernaryMaxHeap<T> = Heap<T, Max, 3>;
 
 /// A minimum heap with branching factor of 4.
-pub type QuaternaryMinHeap<T> = Heap<T, false, 4>;
+pub type QuaternaryMinHeap<T> = Heap<T, Min, 4>;
 
 /// A maximum heap with branching factor of 4.
-pub type QuaternaryMaxHeap<T> = Heap<T, true, 4>;
+pub type QuaternaryMaxHeap<T> = Heap<T, Max, 4>;
 
 /// A minimum heap with branching factor of 5.
-pub type QuinaryMinHeap<T> = Heap<T, false, 5>;
+pub type QuinaryMinHeap<T> = Heap<T, Min, 5>;
 
 /// A maximum heap with branching factor of 5.
-pub type QuinaryMaxHeap<T> = Heap<T, true, 5>;
+pub type QuinaryMaxHeap<T> = Heap<T, Max, 5>;
 
 /// A complete binary tree in which the value of each node in the tree is either
 /// less than (in the case of a minimum heap) or greater than (in the case of a
 /// maximum heap) the value of each of its children. As a consequence, either the
 /// smallest or largest value in the tree is always located at the root of the tree.
-#[derive(Debug, Clone)]
-pub struct Heap<T, const MAX_HEAP: bool, const BRANCHES: usize>
+///
+/// Which element belongs on top is decided by the ordering policy `O`, such as [`Min`] or [`Max`].
+pub struct Heap<T, O, const BRANCHES: usize>
 where
-    T: Ord + Eq + Copy,
+    T: Copy,
+    O: Order<T>,
 {
     heap: Vec<T>,
-    sort_order: Ordering,
+    order: PhantomData<O>,
 }
 
-impl<T, const MAX_HEAP: bool, const BRANCHES: usize> From<&[T]> for Heap<T, MAX_HEAP, BRANCHES>
+impl<T, O, const BRANCHES: usize> Clone for Heap<T, O, BRANCHES>
 where
-    T: Ord + Eq + Copy,
+    T: Copy,
+    O: Order<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            order: PhantomData,
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Debug for Heap<T, O, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Heap").field("heap", &self.heap).finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> From<&[T]> for Heap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
 {
     /// Builds a new Heap object from a slice of type T by cloning the elements in the slice.
     /// 
     /// ## Example:
     /// 
     /// ```
-    /// use rheap::Heap;
-    /// use std::cmp::Ordering;
+    /// use rheap::{Heap, Max, Min};
     /// 
     /// let mut v: Vec<usize> = vec![11, 6, 8, 5, 9, 1, 4, 2, 2, 2, 3, 4, 23, 2, 0, 77];
     /// 
-    /// let mut heap: Heap<usize, false, 2> = Heap::from(&v[..]);
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
     /// assert!(heap.is_valid());
-    /// assert!(heap.sort_order() == Ordering::Less);
+    /// assert!(heap.peek() == Some(&0));
     /// 
-    /// let mut heap: Heap<usize, true, 2> = Heap::from(&v[..]);
+    /// let mut heap: Heap<usize, Max, 2> = Heap::from(&v[..]);
     /// assert!(heap.is_valid());
-    /// assert!(heap.sort_order() == Ordering::Greater);
+    /// assert!(heap.peek() == Some(&77));
     /// 
     /// assert!(heap.len() == v.len())
     /// ```
     fn from(s: &[T]) -> Self {
         let mut heap: Vec<T> = Vec::from(s);
-        let sort_order: Ordering = if MAX_HEAP {
-            Ordering::Greater
-        } else {
-            Ordering::Less
-        };
-        Heap::<T, MAX_HEAP, BRANCHES>::heap_sort(&mut heap[..]);
-        Self { heap, sort_order }
+        Heap::<T, O, BRANCHES>::heap_sort(&mut heap[..]);
+        Self {
+            heap,
+            order: PhantomData,
+        }
     }
 }
 
-impl<T, const MAX_HEAP: bool, const BRANCHES: usize> Heap<T, MAX_HEAP, BRANCHES>
+impl<T, O, const BRANCHES: usize> Default for Heap<T, O, BRANCHES>
 where
-    T: Ord + Eq + Copy,
+    T: Copy,
+    O: Order<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Heap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
 {
     /// Constructs a new, empty heap.
     /// The new heap will allocate memory as elements are inserted.
     pub fn new() -> Self {
         Self {
             heap: Vec::new(),
-            sort_order: if MAX_HEAP {
-                Ordering::Greater
-            } else {
-                Ordering::Less
-            },
+            order: PhantomData,
         }
     }
 
@@ -163,20 +292,95 @@ where
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             heap: Vec::with_capacity(capacity),
-            sort_order: if MAX_HEAP {
-                Ordering::Greater
-            } else {
-                Ordering::Less
-            },
+            order: PhantomData,
         }
     }
 
     /// Moves all the elements of other into self, leaving other empty.
-    pub fn append(&mut self, other: &mut Self) {
+    /// The other heap may have a different number of branches.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let mut binary: Heap<usize, Min, 2> = Heap::from(&[5, 3, 9][..]);
+    /// let mut quaternary: Heap<usize, Min, 4> = Heap::from(&[4, 1][..]);
+    /// binary.append(&mut quaternary);
+    /// assert!(quaternary.is_empty());
+    /// assert!(binary.len() == 5 && binary.peek() == Some(&1));
+    /// ```
+    pub fn append<const D2: usize>(&mut self, other: &mut Heap<T, O, D2>) {
         self.heap.append(&mut other.heap);
         Self::heap_sort(&mut self.heap);
     }
 
+    /// Consumes the heap and returns a heap with the opposite ordering policy, such as a
+    /// minimum heap turned into a maximum heap. The allocation is reused and the heap is rebuilt once.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{BinaryMaxHeap, BinaryMinHeap, Heap};
+    ///
+    /// let heap: BinaryMinHeap<i32> = Heap::from(&[4, -2, 7][..]);
+    /// let heap: BinaryMaxHeap<i32> = heap.into_reversed();
+    /// assert!(heap.peek() == Some(&7));
+    /// ```
+    pub fn into_reversed(self) -> Heap<T, O::Reverse, BRANCHES>
+    where
+        O: ReverseOrder<T>,
+    {
+        self.convert::<O::Reverse, BRANCHES>()
+    }
+
+    /// Consumes the heap and returns a heap with `D2` branches and the same ordering policy.
+    /// The allocation is reused and the heap is rebuilt once, unless the number of branches is unchanged.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{BinaryMinHeap, Heap, QuaternaryMinHeap};
+    ///
+    /// let heap: BinaryMinHeap<u8> = Heap::from(&[6, 2, 8, 1, 9][..]);
+    /// let heap: QuaternaryMinHeap<u8> = heap.into_arity::<4>();
+    /// assert!(heap.is_valid() && heap.peek() == Some(&1));
+    /// ```
+    pub fn into_arity<const D2: usize>(self) -> Heap<T, O, D2> {
+        let mut heap: Vec<T> = self.heap;
+        if D2 != BRANCHES {
+            Heap::<T, O, D2>::heap_sort(&mut heap);
+        }
+        Heap {
+            heap,
+            order: PhantomData,
+        }
+    }
+
+    /// Consumes the heap and returns a heap with the ordering policy `O2` and `D2` branches.
+    /// The allocation is reused and the heap is rebuilt once.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Max, Min};
+    ///
+    /// let heap: Heap<usize, Min, 2> = Heap::from(&[3, 8, 1][..]);
+    /// let heap: Heap<usize, Max, 3> = heap.convert::<Max, 3>();
+    /// assert!(heap.peek() == Some(&8));
+    /// ```
+    pub fn convert<O2, const D2: usize>(self) -> Heap<T, O2, D2>
+    where
+        O2: Order<T>,
+    {
+        let mut heap: Vec<T> = self.heap;
+        Heap::<T, O2, D2>::heap_sort(&mut heap);
+        Heap {
+            heap,
+            order: PhantomData,
+        }
+    }
+
     /// Returns the number of elements the heap can hold without reallocating.
     pub fn capacity(&self) -> usize {
         self.heap.capacity()
@@ -200,30 +404,268 @@ where
         self.heap.truncate(len)
     }
 
-    /// Returns the sort order of the heap.
-    /// `Ordering::Greater` indicates a maximum heap.
-    /// `Ordering::Less` indicates a minimum heap.
-    pub fn sort_order(&self) -> Ordering {
-        self.sort_order
-    }
-
     /// Clears the heap, removing all elements.
     /// Note that this method has no effect on the allocated capacity of the heap.
     pub fn clear(&mut self) {
         self.heap.clear()
     }
 
-    /// Performs a linear search (in O(n) time) to find the index of an element on the heap.
-    /// Returns `None` if the element was not found.
+    /// Removes all elements from the heap and returns them as an iterator in arbitrary order.
+    /// Note that this method has no effect on the allocated capacity of the heap.
+    pub fn drain(&mut self) -> std::vec::Drain<'_, T> {
+        self.heap.drain(..)
+    }
+
+    /// Returns a lazy iterator that removes elements from the heap in priority order.
+    /// Any elements that have not been yielded when the iterator is dropped are removed as well.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![5, 3, 9, 1, 7];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    ///
+    /// let sorted: Vec<usize> = heap.drain_sorted().collect();
+    /// assert!(sorted == vec![1, 3, 5, 7, 9]);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, O, BRANCHES> {
+        DrainSorted { heap: self }
+    }
+
+    /// Returns a lazy iterator that removes elements from the top of the heap, in priority order,
+    /// for as long as they satisfy `predicate`. The first element that does not satisfy it stays on the heap.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// // deadlines of pending timers
+    /// let v: Vec<u64> = vec![40, 10, 30, 20, 50];
+    /// let mut heap: Heap<u64, Min, 4> = Heap::from(&v[..]);
+    ///
+    /// let now: u64 = 30;
+    /// let expired: Vec<u64> = heap.drain_while(|&t| t <= now).collect();
+    /// assert!(expired == vec![10, 20, 30]);
+    /// assert!(heap.len() == 2);
+    /// ```
+    pub fn drain_while<P>(&mut self, predicate: P) -> DrainWhile<'_, T, O, P, BRANCHES>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        DrainWhile {
+            heap: self,
+            predicate,
+            done: false,
+        }
+    }
+
+    /// Removes and returns every element that is tied with the element on top of the heap.
+    /// The ties are found by walking the tree from the root and pruning every subtree whose
+    /// root is already worse than the top, so only the ties and their children are visited.
+    /// Returns an empty vector if the heap is empty.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Max};
+    ///
+    /// let v: Vec<usize> = vec![3, 9, 1, 9, 4, 9, 2];
+    /// let mut heap: Heap<usize, Max, 3> = Heap::from(&v[..]);
+    /// assert!(heap.pop_ties() == vec![9, 9, 9]);
+    /// assert!(heap.peek() == Some(&4));
+    /// ```
+    pub fn pop_ties(&mut self) -> Vec<T> {
+        self.pop_equal_iter().collect()
+    }
+
+    /// Returns a lazy iterator that pops the element on top of the heap along with every element tied with it.
+    /// Elements that have not been yielded when the iterator is dropped stay on the heap.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![2, 5, 2, 7, 2];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    /// assert!(heap.pop_equal_iter().count() == 3);
+    /// assert!(heap.peek() == Some(&5));
+    /// ```
+    pub fn pop_equal_iter(&mut self) -> PopEqual<'_, T, O, BRANCHES> {
+        let ties: Vec<usize> = self.tie_indices();
+        PopEqual {
+            heap: self,
+            ties: ties.into_iter(),
+        }
+    }
+
+    /// Returns the indices of the root and every element tied with it, from the highest index down.
+    fn tie_indices(&self) -> Vec<usize> {
+        let mut ties: Vec<usize> = Vec::new();
+        if let Some(root) = self.heap.first() {
+            let mut stack: Vec<usize> = vec![0];
+            while let Some(index) = stack.pop() {
+                if O::compare(&self.heap[index], root) == std::cmp::Ordering::Equal {
+                    ties.push(index);
+                    let first_child: usize = (index * BRANCHES) + 1;
+                    let last_child: usize = (first_child + BRANCHES).min(self.heap.len());
+                    stack.extend(first_child..last_child);
+                }
+            }
+        }
+        // Removing from the highest index down never disturbs the lower ties: nothing can
+        // sift above a tie, and the elements moved down into the hole are never ties.
+        ties.sort_unstable_by(|a, b| b.cmp(a));
+        ties
+    }
+
+    /// Retains only the elements for which `predicate` returns true.
+    /// The elements are filtered in a single pass and the heap is then rebuilt once.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Max};
+    ///
+    /// let v: Vec<usize> = vec![1, 2, 3, 4, 5, 6];
+    /// let mut heap: Heap<usize, Max, 2> = Heap::from(&v[..]);
+    /// heap.retain(|x| x % 2 == 1);
+    /// assert!(heap.len() == 3);
+    /// assert!(heap.peek() == Some(&5));
+    /// ```
+    pub fn retain<P>(&mut self, mut predicate: P)
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let len: usize = self.heap.len();
+        self.heap.retain(|x| predicate(x));
+        if self.heap.len() != len {
+            Self::heap_sort(&mut self.heap);
+        }
+    }
+
+    /// Retains only the elements for which `predicate` returns true, passing a mutable reference to each element.
+    /// Because the predicate may change the elements, the heap is always rebuilt once after filtering.
+    pub fn retain_mut<P>(&mut self, predicate: P)
+    where
+        P: FnMut(&mut T) -> bool,
+    {
+        self.heap.retain_mut(predicate);
+        Self::heap_sort(&mut self.heap);
+    }
+
+    /// Removes every element for which `predicate` returns true and returns them as a new heap.
+    /// Both heaps are rebuilt with a single `heap_sort` after one pass over the elements.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = (0..10).collect();
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    /// let odd: Heap<usize, Min, 2> = heap.split_off_where(|x| x % 2 == 1);
+    /// assert!(heap.len() == 5 && heap.peek() == Some(&0));
+    /// assert!(odd.len() == 5 && odd.peek() == Some(&1));
+    /// ```
+    pub fn split_off_where<P>(&mut self, mut predicate: P) -> Self
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut other: Self = Self::new();
+        let len: usize = self.heap.len();
+        self.heap.retain(|x| {
+            if predicate(x) {
+                other.heap.push(*x);
+                false
+            } else {
+                true
+            }
+        });
+        if self.heap.len() != len {
+            Self::heap_sort(&mut self.heap);
+            Self::heap_sort(&mut other.heap);
+        }
+        other
+    }
+
+    /// Consumes the heap and splits it into two heaps. The first contains every element for which
+    /// `predicate` returns true and the second contains the rest. The second heap reuses this heap's allocation.
     ///
     /// ## Example:
     ///
     /// ```
-    /// use rheap::Heap;
+    /// use rheap::{Heap, Max};
+    ///
+    /// let v: Vec<i32> = vec![-3, 8, 1, -7, 4];
+    /// let heap: Heap<i32, Max, 3> = Heap::from(&v[..]);
+    /// let (negative, positive) = heap.partition(|x| *x < 0);
+    /// assert!(negative.peek() == Some(&-3));
+    /// assert!(positive.peek() == Some(&8));
+    /// ```
+    pub fn partition<P>(mut self, predicate: P) -> (Self, Self)
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let matching: Self = self.split_off_where(predicate);
+        (matching, self)
+    }
+
+    /// Removes the best `k` elements from the heap and returns them as a new heap.
+    /// If the heap holds `k` elements or fewer, all of them are moved to the new heap.
+    ///
+    /// The elements are popped in priority order, which is already a valid heap layout,
+    /// so this runs in O(k log n) time and the new heap needs no rebuild.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![9, 4, 7, 1, 8, 2];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    /// let best: Heap<usize, Min, 2> = heap.split_top(3);
+    /// assert!(best.as_slice() == &[1, 2, 4]);
+    /// assert!(heap.peek() == Some(&7));
+    /// ```
+    pub fn split_top(&mut self, k: usize) -> Self {
+        if k >= self.heap.len() {
+            return Self {
+                heap: std::mem::take(&mut self.heap),
+                order: PhantomData,
+            };
+        }
+        let mut other: Self = Self::with_capacity(k);
+        while other.heap.len() < k {
+            match self.top() {
+                Some(x) => other.heap.push(x),
+                None => break,
+            }
+        }
+        other
+    }
+
+    /// Searches the heap for an element equal to `element` and returns its index.
+    /// Subtrees whose root is already behind `element` cannot contain it and are skipped,
+    /// so the search only visits the elements that are ahead of or tied with `element`
+    /// and their children. Returns `None` if the element was not found.
+    ///
+    /// The pruning is only correct if `PartialEq` agrees with `O`, meaning that `x == y` implies
+    /// `O::compare(x, y) == Ordering::Equal`. When that does not hold, an element equal to `element`
+    /// may sit in a skipped subtree and is not found; use `iter().position` for a linear search instead.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
     ///
     /// let mut v: Vec<usize> = vec![0, 2, 4, 6, 8, 10];
     /// 
-    /// let mut heap: Heap<usize, false, 2> = Heap::from(&v[..]);
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
     ///
     /// if let Some(index) = heap.find(&6) {
     ///     assert!(index == 3);
@@ -231,8 +673,213 @@ where
     ///     panic!("Did not find the number 6.");
     /// }
     /// ```
-    pub fn find(&self, element: &T) -> Option<usize> {
-        (0..self.heap.len()).find(|&i| self.heap[i] == *element)
+    pub fn find(&self, element: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let mut stack: Vec<usize> = Vec::new();
+        if !self.heap.is_empty() {
+            stack.push(0);
+        }
+        while let Some(index) = stack.pop() {
+            if O::precedes(element, &self.heap[index]) {
+                continue;
+            }
+            if self.heap[index] == *element {
+                return Some(index);
+            }
+            let first_child: usize = (index * BRANCHES) + 1;
+            let last_child: usize = (first_child + BRANCHES).min(self.heap.len());
+            stack.extend((first_child..last_child).rev());
+        }
+        None
+    }
+
+    /// Returns true if the heap contains an element equal to `element`.
+    /// Like [`Heap::find`], the search skips every subtree whose root is already behind `element`,
+    /// so it requires `PartialEq` to agree with `O`.
+    pub fn contains(&self, element: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.find(element).is_some()
+    }
+
+    /// Returns the index of the parent of the element at `index`.
+    /// Returns `None` if `index` is the root or is out of bounds.
+    pub fn parent(&self, index: usize) -> Option<usize> {
+        if index == 0 || index >= self.heap.len() {
+            None
+        } else {
+            Some((index - 1) / BRANCHES)
+        }
+    }
+
+    /// Returns the range of indices of the children of the element at `index`.
+    /// The range is empty if the element has no children or if `index` is out of bounds.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let heap: Heap<usize, Min, 3> = Heap::from(&[0, 1, 2, 3, 4, 5][..]);
+    /// assert!(heap.children(0) == (1..4));
+    /// assert!(heap.children(1) == (4..6));
+    /// assert!(heap.children(2).is_empty());
+    /// assert!(heap.parent(5) == Some(1));
+    /// ```
+    pub fn children(&self, index: usize) -> std::ops::Range<usize> {
+        let len: usize = self.heap.len();
+        if index >= len {
+            return len..len;
+        }
+        let first_child: usize = index.saturating_mul(BRANCHES).saturating_add(1).min(len);
+        let last_child: usize = first_child.saturating_add(BRANCHES).min(len);
+        first_child..last_child
+    }
+
+    /// Returns the number of edges between the root and the element at `index`.
+    /// Returns `None` if `index` is out of bounds.
+    pub fn depth(&self, mut index: usize) -> Option<usize> {
+        if index >= self.heap.len() {
+            return None;
+        }
+        let mut depth: usize = 0;
+        while index > 0 {
+            index = (index - 1) / BRANCHES;
+            depth += 1;
+        }
+        Some(depth)
+    }
+
+    /// Returns the number of levels in the heap's tree, which is zero for an empty heap and one for a heap with only a root.
+    pub fn height(&self) -> usize {
+        match self.heap.len() {
+            0 => 0,
+            len => self.depth(len - 1).unwrap() + 1,
+        }
+    }
+
+    /// Returns an iterator over the levels of the heap's tree, from the root down, each as a slice.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let heap: Heap<usize, Min, 2> = Heap::from(&[0, 1, 2, 3, 4, 5][..]);
+    /// let levels: Vec<&[usize]> = heap.levels().collect();
+    /// assert!(levels == vec![&[0][..], &[1, 2][..], &[3, 4, 5][..]]);
+    /// assert!(heap.height() == 3);
+    /// ```
+    pub fn levels(&self) -> Levels<'_, T, BRANCHES> {
+        Levels {
+            heap: &self.heap,
+            width: 1,
+        }
+    }
+
+    /// Returns an iterator that visits each element before the subtrees of its children,
+    /// yielding the index of each element along with a reference to it.
+    pub fn iter_pre_order(&self) -> PreOrder<'_, T, BRANCHES> {
+        PreOrder {
+            heap: &self.heap,
+            stack: if self.heap.is_empty() {
+                vec![]
+            } else {
+                vec![0]
+            },
+        }
+    }
+
+    /// Returns a [`Cursor`] positioned at the root of the heap.
+    pub fn cursor(&mut self) -> Cursor<'_, T, O, BRANCHES> {
+        Cursor {
+            heap: self,
+            index: 0,
+        }
+    }
+
+    /// Returns an iterator that borrows the heap and yields its elements in priority order.
+    /// Neither the heap nor its elements are cloned or modified, and yielding the first k
+    /// elements costs O(k log k) time. Elements that are tied are yielded in no particular order.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<u32> = vec![30, 10, 50, 20, 40];
+    /// let heap: Heap<u32, Min, 2> = Heap::from(&v[..]);
+    ///
+    /// let next: Vec<&u32> = heap.iter_sorted().take(3).collect();
+    /// assert!(next == vec![&10, &20, &30]);
+    /// assert!(heap.len() == 5);
+    /// ```
+    pub fn iter_sorted(&self) -> IterSorted<'_, T, O, BRANCHES> {
+        IterSorted {
+            heap: &self.heap,
+            frontier: if self.heap.is_empty() {
+                vec![]
+            } else {
+                vec![0]
+            },
+            remaining: self.heap.len(),
+            order: PhantomData,
+        }
+    }
+
+    /// Returns references to the best `k` elements on the heap in priority order, or to all of them if there are fewer than `k`.
+    /// See [`Heap::iter_sorted`].
+    pub fn top_k_ref(&self, k: usize) -> Vec<&T> {
+        self.iter_sorted().take(k).collect()
+    }
+
+    /// Returns an iterator over every element that is strictly ahead of `bound`, in no particular order.
+    /// Subtrees whose root is not ahead of `bound` are skipped, so the cost is proportional to the
+    /// number of elements yielded times the branching factor rather than to the size of the heap.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// // deadlines of pending jobs
+    /// let v: Vec<u64> = vec![12, 40, 7, 33, 18, 5, 60];
+    /// let heap: Heap<u64, Min, 4> = Heap::from(&v[..]);
+    ///
+    /// let mut due: Vec<u64> = heap.iter_better_than(&18).copied().collect();
+    /// due.sort();
+    /// assert!(due == vec![5, 7, 12]);
+    /// ```
+    pub fn iter_better_than(&self, bound: &T) -> BetterThan<'_, T, O, BRANCHES> {
+        BetterThan {
+            heap: &self.heap,
+            bound: *bound,
+            stack: if self.heap.is_empty() {
+                vec![]
+            } else {
+                vec![0]
+            },
+            order: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements that are strictly ahead of `bound`.
+    /// See [`Heap::iter_better_than`] for how the tree is pruned.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Max};
+    ///
+    /// let v: Vec<usize> = vec![3, 8, 1, 9, 4];
+    /// let heap: Heap<usize, Max, 2> = Heap::from(&v[..]);
+    /// assert!(heap.count_better_than(&4) == 2);
+    /// ```
+    pub fn count_better_than(&self, bound: &T) -> usize {
+        self.iter_better_than(bound).count()
     }
 
     /// Inserts an element into the heap.
@@ -240,12 +887,11 @@ where
     /// ## Example:
     ///
     /// ```
-    /// use rheap::Heap;
-    /// use std::cmp::Ordering;
+    /// use rheap::{Heap, Min};
     ///
     /// let mut v: Vec<usize> = vec![0, 2, 4, 6, 8, 10];
     /// 
-    /// let mut heap: Heap<usize, false, 2> = Heap::from(&v[..]);
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
     /// 
     /// heap.insert(5);
     /// 
@@ -256,9 +902,7 @@ where
     /// }
     /// ```
     pub fn insert(&mut self, element: T) {
-        let index: usize = self.heap.len();
-        self.heap.push(element);
-        Self::sort_up(&mut self.heap, self.sort_order, index)
+        self.insert_hooked(element, &mut ());
     }
 
     /// Returns true if the heap contains no elements.
@@ -286,12 +930,11 @@ where
     /// ## Example:
     ///
     /// ```
-    /// use rheap::Heap;
-    /// use std::cmp::Ordering;
+    /// use rheap::{Heap, Min};
     ///
     /// let mut v: Vec<usize> = vec![0, 2, 4, 6, 8, 10];
     /// 
-    /// let mut heap: Heap<usize, false, 2> = Heap::from(&v[..]);
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
     /// 
     /// if let Ok(old_element) = heap.remove(3) {
     ///     assert!(old_element == 6);
@@ -312,29 +955,94 @@ where
                 "Index is beyond the end of the heap.",
             ))
         } else {
-            let removed: T = self.heap.swap_remove(index);
-            if index < self.heap.len() {
-                if self.heap[index].cmp(&removed) == self.sort_order {
-                    Self::sort_up(&mut self.heap, self.sort_order, index);
-                } else {
-                    Self::sort_down(&mut self.heap, self.sort_order, index);
-                }
-            }
-            Ok(removed)
+            Ok(self.remove_hooked(index, &mut ()))
         }
     }
 
+    /// Finds an element equal to `element` with [`Heap::find`], removes it from the heap and returns it.
+    /// Returns an error if no such element is on the heap.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{ErrorKind, Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![3, 1, 4, 1, 5];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    /// assert!(heap.remove_item(&4).unwrap() == 4);
+    /// assert!(heap.remove_item(&4).unwrap_err().kind() == ErrorKind::NotFound);
+    /// ```
+    pub fn remove_item(&mut self, element: &T) -> Result<T>
+    where
+        T: PartialEq,
+    {
+        let index: usize = self.find_item(element)?;
+        self.remove(index)
+    }
+
+    /// Removes every element equal to `element` and returns how many were removed.
+    /// The elements are removed in a single pass and the heap is then rebuilt once.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![3, 1, 4, 1, 5, 1];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    /// assert!(heap.remove_all(&1) == 3);
+    /// assert!(heap.peek() == Some(&3));
+    /// ```
+    pub fn remove_all(&mut self, element: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        let len: usize = self.heap.len();
+        self.retain(|x| x != element);
+        len - self.heap.len()
+    }
+
+    /// Removes every element for which `predicate` returns true and returns them in arbitrary order.
+    /// The elements are removed in a single pass and the heap is then rebuilt once.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Max};
+    ///
+    /// let v: Vec<usize> = vec![10, 21, 32, 43, 54];
+    /// let mut heap: Heap<usize, Max, 3> = Heap::from(&v[..]);
+    /// let mut odd: Vec<usize> = heap.remove_where(|x| x % 2 == 1);
+    /// odd.sort();
+    /// assert!(odd == vec![21, 43]);
+    /// assert!(heap.peek() == Some(&54));
+    /// ```
+    pub fn remove_where<P>(&mut self, mut predicate: P) -> Vec<T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut removed: Vec<T> = Vec::new();
+        self.retain(|x| {
+            if predicate(x) {
+                removed.push(*x);
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+
     /// Removes and returns the element from the top of the heap. Returns `None` if the heap is empty.
     ///
     /// ## Example:
     ///
     /// ```
-    /// use rheap::Heap;
-    /// use std::cmp::Ordering;
+    /// use rheap::{Heap, Min};
     ///
     /// let mut v: Vec<usize> = vec![0, 2, 4, 6, 8, 10];
     /// 
-    /// let mut heap: Heap<usize, false, 2> = Heap::from(&v[..]);
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
     /// 
     /// if let Some(smallest) = heap.top() {
     ///     assert!(smallest == 0);
@@ -346,24 +1054,89 @@ where
         if self.heap.is_empty() {
             None
         } else {
-            let removed: T = self.heap.swap_remove(0);
-            Self::sort_down(&mut self.heap, self.sort_order, 0);
+            Some(self.remove_hooked(0, &mut ()))
+        }
+    }
+
+    /// Inserts `element` and then removes and returns the element on top of the heap,
+    /// sifting at most once. If `element` would itself end up on top, it is returned
+    /// immediately and the heap is left untouched.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// // keep the three largest values seen so far
+    /// let mut top3: Heap<usize, Min, 2> = Heap::new();
+    /// for x in [5, 1, 8, 3, 9, 2] {
+    ///     if top3.len() < 3 {
+    ///         top3.insert(x);
+    ///     } else {
+    ///         top3.push_pop(x);
+    ///     }
+    /// }
+    /// assert!(top3.drain_sorted().collect::<Vec<usize>>() == vec![5, 8, 9]);
+    /// ```
+    pub fn push_pop(&mut self, element: T) -> T {
+        match self.heap.first() {
+            Some(root) if O::precedes(root, &element) => {
+                let removed: T = std::mem::replace(&mut self.heap[0], element);
+                Self::sort_down(&mut self.heap, 0);
+                removed
+            }
+            _ => element,
+        }
+    }
+
+    /// Removes and returns the element on top of the heap and inserts `element` in its place, sifting once.
+    /// If the heap is empty, `element` is inserted and `None` is returned.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![2, 4, 6];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    /// assert!(heap.replace_top(5) == Some(2));
+    /// assert!(heap.peek() == Some(&4));
+    /// ```
+    pub fn replace_top(&mut self, element: T) -> Option<T> {
+        if self.heap.is_empty() {
+            self.heap.push(element);
+            None
+        } else {
+            let removed: T = std::mem::replace(&mut self.heap[0], element);
+            Self::sort_down(&mut self.heap, 0);
             Some(removed)
         }
     }
 
+    /// Removes and returns the element on top of the heap and inserts `element` in its place, sifting once.
+    /// Returns an error, without inserting `element`, if the heap is empty.
+    pub fn try_replace_top(&mut self, element: T) -> Result<T> {
+        if self.heap.is_empty() {
+            Err(Error::new(
+                ErrorKind::EmptyHeap,
+                "Can not replace the top of an empty heap.",
+            ))
+        } else {
+            Ok(self.replace_top(element).unwrap())
+        }
+    }
+
     /// Updates the value (or "priority") of the element at `index`.
     /// Returns an error if the element is not found in the heap or the index is out of bounds.
     ///
     /// ## Example:
     ///
     /// ```
-    /// use rheap::Heap;
-    /// use std::cmp::Ordering;
+    /// use rheap::{Heap, Min};
     ///
     /// let mut v: Vec<usize> = vec![0, 2, 4, 6, 8, 10];
     /// 
-    /// let mut heap: Heap<usize, false, 3> = Heap::from(&v[..]);
+    /// let mut heap: Heap<usize, Min, 3> = Heap::from(&v[..]);
     /// 
     /// if heap.update(3, |x| *x = 11).is_err() {
     ///     panic!();
@@ -376,6 +1149,234 @@ where
     where
         F: Fn(&mut T),
     {
+        self.check_index(index)?;
+        modifier(&mut self.heap[index]);
+        self.restore(index);
+        Ok(())
+    }
+
+    /// Updates the value (or "priority") of the element at `index` with a closure that is called once,
+    /// and returns the element's index after the heap property has been restored along with the closure's output.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![0, 2, 4, 6, 8, 10];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    ///
+    /// let mut log: Vec<usize> = Vec::new();
+    /// let (index, old) = heap
+    ///     .update_with(5, |x| {
+    ///         log.push(*x);
+    ///         std::mem::replace(x, 1)
+    ///     })
+    ///     .unwrap();
+    /// assert!(old == 10 && index == 2 && log == vec![10]);
+    /// assert!(heap.as_slice()[index] == 1);
+    /// ```
+    pub fn update_with<F, R>(&mut self, index: usize, modifier: F) -> Result<(usize, R)>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.check_index(index)?;
+        let output: R = modifier(&mut self.heap[index]);
+        Ok((self.restore(index), output))
+    }
+
+    /// Updates the value (or "priority") of the element at `index` with a fallible closure that is called once.
+    ///
+    /// The outer result is an error if the heap is empty or if the index is out of bounds.
+    /// The inner result holds what the closure returned. On success it also holds the element's
+    /// index after the heap property has been restored. If the closure returns an error, the element
+    /// is restored to its old value and stays where it was, so the heap is left unchanged.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<u32> = vec![5, 10, 15];
+    /// let mut heap: Heap<u32, Min, 2> = Heap::from(&v[..]);
+    ///
+    /// let result = heap.try_update(1, |x| {
+    ///     *x = 0;
+    ///     Err::<(), &str>("rejected")
+    /// });
+    /// assert!(result.unwrap() == Err("rejected"));
+    /// assert!(heap.as_slice() == &v[..]);
+    ///
+    /// let result = heap.try_update(2, |x| x.checked_sub(14).map(|y| *x = y).ok_or("underflow"));
+    /// assert!(result.unwrap() == Ok((0, ())));
+    /// assert!(heap.peek() == Some(&1));
+    /// ```
+    pub fn try_update<F, R, E>(
+        &mut self,
+        index: usize,
+        modifier: F,
+    ) -> Result<std::result::Result<(usize, R), E>>
+    where
+        F: FnOnce(&mut T) -> std::result::Result<R, E>,
+    {
+        self.check_index(index)?;
+        let old: T = self.heap[index];
+        match modifier(&mut self.heap[index]) {
+            Ok(output) => Ok(Ok((self.restore(index), output))),
+            Err(e) => {
+                self.heap[index] = old;
+                Ok(Err(e))
+            }
+        }
+    }
+
+    /// Updates the values (or "priorities") of the elements at `indices`, which refer to positions
+    /// before any of the updates are applied. An index that appears more than once is updated more than once.
+    /// Returns an error, without modifying anything, if any index is out of bounds.
+    ///
+    /// All modifications are applied first. The heap property is then restored either by sifting
+    /// only the modified elements and their ancestors or, when that would touch a large part of
+    /// the heap, by a single `heap_sort` rebuild.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![1, 2, 3, 4, 5, 6, 7, 8];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    /// heap.update_many([0, 3, 5], |x| *x += 10).unwrap();
+    /// assert!(heap.is_valid());
+    /// assert!(heap.peek() == Some(&2));
+    /// ```
+    pub fn update_many<I, F>(&mut self, indices: I, mut modifier: F) -> Result<()>
+    where
+        I: IntoIterator<Item = usize>,
+        F: FnMut(&mut T),
+    {
+        let indices: Vec<usize> = indices.into_iter().collect();
+        for &index in indices.iter() {
+            self.check_index(index)?;
+        }
+        for &index in indices.iter() {
+            modifier(&mut self.heap[index]);
+        }
+        self.repair(indices);
+        Ok(())
+    }
+
+    /// Updates the value (or "priority") of every element on the heap and then rebuilds the heap once.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Max};
+    ///
+    /// let v: Vec<i32> = vec![1, 5, 3];
+    /// let mut heap: Heap<i32, Max, 2> = Heap::from(&v[..]);
+    /// heap.update_all(|x| *x = -*x);
+    /// assert!(heap.peek() == Some(&-1));
+    /// ```
+    pub fn update_all<F>(&mut self, modifier: F)
+    where
+        F: FnMut(&mut T),
+    {
+        self.heap.iter_mut().for_each(modifier);
+        Self::heap_sort(&mut self.heap);
+    }
+
+    /// Returns a guard that gives mutable access to every element on the heap as a slice.
+    /// The heap property is restored with a single `heap_sort` when the guard is dropped.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![10, 20, 30, 40];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    /// {
+    ///     let mut slice = heap.as_mut_slice();
+    ///     slice[0] = 99;
+    ///     slice[3] = 5;
+    /// }
+    /// assert!(heap.peek() == Some(&5));
+    /// assert!(heap.is_valid());
+    /// ```
+    pub fn as_mut_slice(&mut self) -> SliceMut<'_, T, O, BRANCHES> {
+        SliceMut { heap: self }
+    }
+
+    /// Restores the heap property after the elements at `modified` have been changed in place.
+    ///
+    /// Only the modified elements and their ancestors can be out of place. Sifting each of
+    /// them down, from the highest index to the lowest, is the same bottom-up pass that
+    /// `heap_sort` performs but restricted to the affected subtrees. When that set of nodes
+    /// is a sizeable fraction of the heap, a full `heap_sort` is cheaper.
+    fn repair(&mut self, modified: Vec<usize>) {
+        let len: usize = self.heap.len();
+        let mut depth: usize = 1;
+        let mut level: usize = 1;
+        while level < len {
+            level = level.saturating_mul(BRANCHES).saturating_add(1);
+            depth += 1;
+        }
+        if modified.len().saturating_mul(depth) >= len / 2 {
+            Self::heap_sort(&mut self.heap);
+            return;
+        }
+        let mut dirty: Vec<usize> = Vec::with_capacity(modified.len() * depth);
+        for mut index in modified {
+            dirty.push(index);
+            while index > 0 {
+                index = (index - 1) / BRANCHES;
+                dirty.push(index);
+            }
+        }
+        dirty.sort_unstable_by(|a, b| b.cmp(a));
+        dirty.dedup();
+        for index in dirty {
+            Self::sort_down(&mut self.heap, index);
+        }
+    }
+
+    /// Finds an element equal to `element` with [`Heap::find`] and updates its value (or "priority").
+    /// Returns an error if no such element is on the heap.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let v: Vec<usize> = vec![4, 8, 15, 16, 23, 42];
+    /// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+    /// heap.update_item(&23, |x| *x = 1).unwrap();
+    /// assert!(heap.peek() == Some(&1));
+    /// assert!(heap.update_item(&99, |x| *x = 0).is_err());
+    /// ```
+    pub fn update_item<F>(&mut self, element: &T, modifier: F) -> Result<()>
+    where
+        T: PartialEq,
+        F: Fn(&mut T),
+    {
+        let index: usize = self.find_item(element)?;
+        self.update(index, modifier)
+    }
+
+    /// Returns the index of an element equal to `element`, or a `NotFound` error.
+    fn find_item(&self, element: &T) -> Result<usize>
+    where
+        T: PartialEq,
+    {
+        self.find(element).ok_or(Error::new(
+            ErrorKind::NotFound,
+            "The element is not on the heap.",
+        ))
+    }
+
+    /// Returns an error if the heap is empty or if `index` is out of bounds.
+    pub(crate) fn check_index(&self, index: usize) -> Result<()> {
         if self.heap.is_empty() {
             Err(Error::new(
                 ErrorKind::EmptyHeap,
@@ -387,15 +1388,55 @@ where
                 "Index is beyond the end of the heap.",
             ))
         } else {
-            modifier(&mut self.heap[index]);
-            if index == 0
-                || self.heap[index].cmp(&self.heap[(index - 1) / BRANCHES]) != self.sort_order
-            {
-                Self::sort_down(&mut self.heap, self.sort_order, index);
+            Ok(())
+        }
+    }
+
+    /// Restores the heap property after the element at `index` has been modified
+    /// and returns the element's new index.
+    fn restore(&mut self, index: usize) -> usize {
+        self.restore_hooked(index, &mut ())
+    }
+
+    /// Pushes `element` onto the heap, sifts it up and returns its final index, reporting every change to `hook`.
+    pub(crate) fn insert_hooked<H: SiftHook<T>>(&mut self, element: T, hook: &mut H) -> usize {
+        let index: usize = self.heap.len();
+        self.heap.push(element);
+        hook.inserted(index, &self.heap[index]);
+        Self::sift_up_hooked(&mut self.heap, index, hook)
+    }
+
+    /// Removes and returns the element at `index`, which must be in bounds, reporting every change to `hook`.
+    /// The last element takes its place and is sifted in whichever direction restores the heap property.
+    pub(crate) fn remove_hooked<H: SiftHook<T>>(&mut self, index: usize, hook: &mut H) -> T {
+        let last: usize = self.heap.len() - 1;
+        if index != last {
+            self.heap.swap(index, last);
+            hook.swapped(&self.heap, index, last);
+        }
+        let removed: T = self.heap.pop().unwrap();
+        hook.removed(last, &removed);
+        if index < last {
+            hook.compared();
+            if O::precedes(&self.heap[index], &removed) {
+                Self::sift_up_hooked(&mut self.heap, index, hook);
             } else {
-                Self::sort_up(&mut self.heap, self.sort_order, index);
+                Self::sift_down_hooked(&mut self.heap, index, hook);
             }
-            Ok(())
+        }
+        removed
+    }
+
+    /// Restores the heap property after the element at `index` has been modified,
+    /// reporting every change to `hook`, and returns the element's new index.
+    pub(crate) fn restore_hooked<H: SiftHook<T>>(&mut self, index: usize, hook: &mut H) -> usize {
+        if index > 0 {
+            hook.compared();
+        }
+        if index == 0 || !O::precedes(&self.heap[index], &self.heap[(index - 1) / BRANCHES]) {
+            Self::sift_down_hooked(&mut self.heap, index, hook)
+        } else {
+            Self::sift_up_hooked(&mut self.heap, index, hook)
         }
     }
 
@@ -408,37 +1449,51 @@ where
     /// ## Example:
     ///
     /// ```
-    /// use rheap::Heap;
-    /// use std::cmp::Ordering;
+    /// use rheap::{Heap, Min};
     ///
     /// let mut heap: Vec<usize> = vec![0, 1, 2, 3, 4, 5];
     /// 
     /// // remove the element located at index 0
     /// let index: usize = 0;
     /// heap.swap_remove(index);
-    /// Heap::<usize, false, 3>::sort_down(&mut heap, Ordering::Less, index);
+    /// Heap::<usize, Min, 3>::sort_down(&mut heap, index);
     /// assert!(heap[0] == 1);
     /// ```
-    pub fn sort_down(heap: &mut [T], sort_order: Ordering, mut index: usize)
-    where
-        T: Ord,
-    {
+    pub fn sort_down(heap: &mut [T], index: usize) {
+        Self::sift_down(heap, index);
+    }
+
+    /// Sorts the heap by iterating down the tree starting at `index` and returns the element's final index.
+    fn sift_down(heap: &mut [T], index: usize) -> usize {
+        Self::sift_down_hooked(heap, index, &mut ())
+    }
+
+    /// Sorts the heap by iterating down the tree starting at `index`, reporting every change to `hook`,
+    /// and returns the element's final index.
+    pub(crate) fn sift_down_hooked<H: SiftHook<T>>(
+        heap: &mut [T],
+        mut index: usize,
+        hook: &mut H,
+    ) -> usize {
         let length: usize = heap.len();
         loop {
             let first_child: usize = (index * BRANCHES) + 1;
             let last_child: usize = first_child + BRANCHES;
             let mut priority: usize = index;
             for i in first_child..last_child.min(length) {
-                priority = if heap[priority].cmp(&heap[i]) == sort_order {
+                hook.compared();
+                priority = if O::precedes(&heap[priority], &heap[i]) {
                     priority
                 } else {
                     i
                 };
             }
             if priority == index {
-                break;
+                return index;
             }
             heap.swap(priority, index);
+            hook.swapped(heap, priority, index);
+            hook.traversed();
             index = priority;
         }
     }
@@ -452,28 +1507,43 @@ where
     /// ## Example:
     ///
     /// ```
-    /// use rheap::Heap;
-    /// use std::cmp::Ordering;
+    /// use rheap::{Heap, Min};
     ///
     /// let mut heap: Vec<usize> = vec![0, 2, 4, 6, 8, 10];
     /// let index: usize = heap.len();
     /// heap.push(5);
-    /// Heap::<usize, false, 3>::sort_up(&mut heap, Ordering::Less, index);
+    /// Heap::<usize, Min, 3>::sort_up(&mut heap, index);
     /// assert!(heap[0] == 0);
     /// ```
-    pub fn sort_up(heap: &mut [T], sort_order: Ordering, mut index: usize)
-    where
-        T: Ord,
-    {
+    pub fn sort_up(heap: &mut [T], index: usize) {
+        Self::sift_up(heap, index);
+    }
+
+    /// Sorts the heap by iterating up the tree starting at `index` and returns the element's final index.
+    fn sift_up(heap: &mut [T], index: usize) -> usize {
+        Self::sift_up_hooked(heap, index, &mut ())
+    }
+
+    /// Sorts the heap by iterating up the tree starting at `index`, reporting every change to `hook`,
+    /// and returns the element's final index.
+    pub(crate) fn sift_up_hooked<H: SiftHook<T>>(
+        heap: &mut [T],
+        mut index: usize,
+        hook: &mut H,
+    ) -> usize {
         while index > 0 {
             let p: usize = (index - 1) / BRANCHES; // calculate the index of the parent node
-            if heap[index].cmp(&heap[p]) == sort_order {
+            hook.compared();
+            if O::precedes(&heap[index], &heap[p]) {
                 heap.swap(index, p); // if the child is smaller than the parent, then swap them
+                hook.swapped(heap, index, p);
+                hook.traversed();
             } else {
                 break;
             }
             index = p;
         }
+        index
     }
 
     /// Performs an in-place heap sort.
@@ -481,27 +1551,23 @@ where
     /// ## Example:
     ///
     /// ```
-    /// use rheap::Heap;
-    /// use std::cmp::Ordering;
+    /// use rheap::{Heap, Min};
     ///
     /// let mut heap: Vec<usize> = vec![8, 66, 9, 55, 7, 0, 14, 6, 37, 2];
-    /// Heap::<usize, false, 3>::heap_sort(&mut heap);
+    /// Heap::<usize, Min, 3>::heap_sort(&mut heap);
     /// assert!(heap[0] == 0);
     /// ```
-    pub fn heap_sort(heap: &mut [T])
-    where
-        T: Ord,
-    {
+    pub fn heap_sort(heap: &mut [T]) {
+        Self::heap_sort_hooked(heap, &mut ());
+    }
+
+    /// Performs an in-place heap sort, reporting every change to `hook`.
+    pub(crate) fn heap_sort_hooked<H: SiftHook<T>>(heap: &mut [T], hook: &mut H) {
         let len: usize = heap.len();
         if len > 1 {
-            let sort_order: Ordering = if MAX_HEAP {
-                Ordering::Greater
-            } else {
-                Ordering::Less
-            };
             let parent: usize = (len - 2) / BRANCHES;
             for index in (0..=parent).rev() {
-                Self::sort_down(heap, sort_order, index);
+                Self::sift_down_hooked(heap, index, hook);
             }
         }
     }
@@ -512,20 +1578,45 @@ where
     /// ## Example:
     ///
     /// ```
-    /// use rheap::Heap;
-    /// use std::cmp::Ordering;
+    /// use rheap::{Heap, Min};
     ///
     /// let mut v: Vec<usize> = Vec::new();
-    /// let mut heap: Heap<usize, false, 3> = Heap::from(&v[..]);
+    /// let mut heap: Heap<usize, Min, 3> = Heap::from(&v[..]);
     /// assert!(heap.is_valid());
     /// ```
     #[doc(hidden)]
     pub fn is_valid(&self) -> bool {
         for i in 1..self.heap.len() {
-            if self.heap[i].cmp(&self.heap[0]) == self.sort_order {
+            if O::precedes(&self.heap[i], &self.heap[0]) {
                 return false;
             }
         }
         true
     }
 }
+
+impl<T, O, const BRANCHES: usize> PriorityQueue<T> for Heap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn push(&mut self, element: T) {
+        self.insert(element)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        Heap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.top()
+    }
+
+    fn len(&self) -> usize {
+        Heap::len(self)
+    }
+
+    fn clear(&mut self) {
+        Heap::clear(self)
+    }
+}
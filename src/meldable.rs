@@ -0,0 +1,580 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{AddressablePriorityQueue, Error, ErrorKind, Min, Order, PriorityQueue, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The source of the IDs that tie every node, and every [`MeldableHandle`], to the arena that stores it.
+static NEXT_ARENA_ID: AtomicU64 = AtomicU64::new(0);
+
+fn next_arena_id() -> u64 {
+    NEXT_ARENA_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A handle to an element stored in a [`RandomizedMeldableHeap`].
+/// Handles remain valid until the element they refer to is removed from the heap, including after
+/// the heap is melded into another one, and are rejected by every heap that does not hold the element.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MeldableHandle {
+    arena: u64,
+    index: usize,
+    generation: u64,
+}
+
+/// The address of a node: the ID of the arena that stores it and its slot in that arena.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct NodeRef {
+    arena: u64,
+    index: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    parent: Option<NodeRef>,
+    left: Option<NodeRef>,
+    right: Option<NodeRef>,
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u64,
+    node: Option<Node<T>>,
+}
+
+/// The slots of one heap's nodes. An arena is never copied: melding moves whole arenas between heaps.
+#[derive(Debug, Clone)]
+struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    live: usize,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            live: 0,
+        }
+    }
+}
+
+/// A heap-ordered binary tree in which two trees are merged by recursively
+/// melding one of them into a randomly chosen subtree of the other. The expected
+/// depth of every path followed by a meld is O(log n), which gives expected
+/// O(log n) `meld`, `insert`, `pop` and `remove` without any amortization.
+///
+/// Nodes live in arenas owned by the heap and are addressed by [`MeldableHandle`]s. New elements go
+/// into the heap's own arena. `meld` takes over the arenas of the other heap instead of copying its
+/// nodes, moving the smaller set of arenas into the larger one, so besides the expected O(log n)
+/// comparisons it only costs time proportional to the number of heaps previously melded into the
+/// smaller side. An adopted arena is dropped as soon as its last element is removed.
+/// The random number generator is seedable so that the shape of the tree is reproducible.
+pub struct RandomizedMeldableHeap<T, O = Min>
+where
+    O: Order<T>,
+{
+    home: u64,
+    arenas: HashMap<u64, Arena<T>>,
+    root: Option<NodeRef>,
+    len: usize,
+    rng: StdRng,
+    order: PhantomData<O>,
+}
+
+impl<T, O> Clone for RandomizedMeldableHeap<T, O>
+where
+    T: Clone,
+    O: Order<T>,
+{
+    /// Clones the heap into fresh arenas, so handles issued by the clone and by the original
+    /// are never accepted by the other.
+    fn clone(&self) -> Self {
+        let ids: HashMap<u64, u64> = self
+            .arenas
+            .keys()
+            .map(|&id| (id, next_arena_id()))
+            .collect();
+        let map = |link: Option<NodeRef>| {
+            link.map(|r| NodeRef {
+                arena: ids[&r.arena],
+                index: r.index,
+            })
+        };
+        let arenas: HashMap<u64, Arena<T>> = self
+            .arenas
+            .iter()
+            .map(|(id, arena)| {
+                let slots: Vec<Slot<T>> = arena
+                    .slots
+                    .iter()
+                    .map(|slot| Slot {
+                        generation: slot.generation,
+                        node: slot.node.as_ref().map(|n| Node {
+                            value: n.value.clone(),
+                            parent: map(n.parent),
+                            left: map(n.left),
+                            right: map(n.right),
+                        }),
+                    })
+                    .collect();
+                let arena: Arena<T> = Arena {
+                    slots,
+                    free: arena.free.clone(),
+                    live: arena.live,
+                };
+                (ids[id], arena)
+            })
+            .collect();
+        Self {
+            home: ids[&self.home],
+            arenas,
+            root: map(self.root),
+            len: self.len,
+            rng: self.rng.clone(),
+            order: PhantomData,
+        }
+    }
+}
+
+impl<T, O> Debug for RandomizedMeldableHeap<T, O>
+where
+    T: Debug,
+    O: Order<T>,
+{
+    /// Formats the shape of the tree as its pre-order walk, with `None` for every missing child,
+    /// leaving out the arena IDs so that two heaps built from the same seed and operations print the same.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tree: Vec<Option<&T>> = Vec::new();
+        let mut stack: Vec<Option<NodeRef>> = vec![self.root];
+        while let Some(link) = stack.pop() {
+            match link {
+                Some(r) => {
+                    let n: &Node<T> = self.node(r);
+                    tree.push(Some(&n.value));
+                    stack.push(n.right);
+                    stack.push(n.left);
+                }
+                None => tree.push(None),
+            }
+        }
+        f.debug_struct("RandomizedMeldableHeap")
+            .field("len", &self.len)
+            .field("tree", &tree)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, O> Default for RandomizedMeldableHeap<T, O>
+where
+    O: Order<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O> RandomizedMeldableHeap<T, O>
+where
+    O: Order<T>,
+{
+    /// Constructs a new, empty heap whose random number generator is seeded from system entropy.
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Constructs a new, empty heap whose random number generator is seeded with `seed`.
+    /// Two heaps built with the same seed and fed the same operations have identical shapes.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::RandomizedMeldableHeap;
+    ///
+    /// let mut heap: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(42);
+    /// heap.insert(5);
+    /// heap.insert(1);
+    /// heap.insert(3);
+    /// assert!(heap.pop() == Some(1));
+    /// ```
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rng: StdRng) -> Self {
+        let home: u64 = next_arena_id();
+        let mut arenas: HashMap<u64, Arena<T>> = HashMap::new();
+        arenas.insert(home, Arena::default());
+        Self {
+            home,
+            arenas,
+            root: None,
+            len: 0,
+            rng,
+            order: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all elements from the heap, invalidating every outstanding handle.
+    pub fn clear(&mut self) {
+        let home: u64 = self.home;
+        self.arenas.retain(|&id, _| id == home);
+        let arena: &mut Arena<T> = self.arenas.get_mut(&home).unwrap();
+        for (index, slot) in arena.slots.iter_mut().enumerate() {
+            if slot.node.take().is_some() {
+                slot.generation += 1;
+                arena.free.push(index);
+            }
+        }
+        arena.live = 0;
+        self.root = None;
+        self.len = 0;
+    }
+
+    /// Returns an immutable reference to the element on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.root.map(|r| &self.node(r).value)
+    }
+
+    /// Returns an immutable reference to the element referred to by `handle`,
+    /// or `None` if the element has already been removed.
+    pub fn get(&self, handle: MeldableHandle) -> Option<&T> {
+        self.resolve(handle).map(|r| &self.node(r).value)
+    }
+
+    /// Returns true if `handle` still refers to an element on the heap.
+    pub fn contains(&self, handle: MeldableHandle) -> bool {
+        self.resolve(handle).is_some()
+    }
+
+    /// Inserts an element into the heap and returns a handle that can later be passed to [`RandomizedMeldableHeap::remove`].
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Max, RandomizedMeldableHeap};
+    ///
+    /// let mut heap: RandomizedMeldableHeap<usize, Max> = RandomizedMeldableHeap::with_seed(7);
+    /// let handle = heap.insert(9);
+    /// heap.insert(4);
+    /// assert!(heap.get(handle) == Some(&9));
+    /// assert!(heap.peek() == Some(&9));
+    /// ```
+    pub fn insert(&mut self, element: T) -> MeldableHandle {
+        let node: Node<T> = Node {
+            value: element,
+            parent: None,
+            left: None,
+            right: None,
+        };
+        let arena: &mut Arena<T> = self.arenas.get_mut(&self.home).unwrap();
+        let index: usize = if let Some(index) = arena.free.pop() {
+            arena.slots[index].node = Some(node);
+            index
+        } else {
+            arena.slots.push(Slot {
+                generation: 0,
+                node: Some(node),
+            });
+            arena.slots.len() - 1
+        };
+        arena.live += 1;
+        let generation: u64 = arena.slots[index].generation;
+        let r: NodeRef = NodeRef {
+            arena: self.home,
+            index,
+        };
+        self.root = self.merge(self.root, Some(r));
+        self.set_parent(self.root, None);
+        self.len += 1;
+        MeldableHandle {
+            arena: self.home,
+            index,
+            generation,
+        }
+    }
+
+    /// Removes and returns the element from the top of the heap. Returns `None` if the heap is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.root.map(|root| self.unlink(root))
+    }
+
+    /// Removes and returns the element referred to by `handle`.
+    /// Returns an error if the handle does not refer to an element on the heap.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::RandomizedMeldableHeap;
+    ///
+    /// let mut heap: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(1);
+    /// heap.insert(2);
+    /// let handle = heap.insert(6);
+    /// heap.insert(4);
+    /// assert!(heap.remove(handle).unwrap() == 6);
+    /// assert!(heap.remove(handle).is_err());
+    /// assert!(heap.len() == 2);
+    /// ```
+    pub fn remove(&mut self, handle: MeldableHandle) -> Result<T> {
+        match self.resolve(handle) {
+            Some(r) => Ok(self.unlink(r)),
+            None => Err(Error::new(
+                ErrorKind::InvalidHandle,
+                "Handle does not refer to an element on the heap.",
+            )),
+        }
+    }
+
+    /// Updates the value (or "priority") of the element referred to by `handle`.
+    /// The handle remains valid after the update.
+    /// Returns an error if the handle does not refer to an element on the heap.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::RandomizedMeldableHeap;
+    ///
+    /// let mut heap: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(5);
+    /// heap.insert(3);
+    /// let handle = heap.insert(7);
+    /// heap.update(handle, |x| *x = 1).unwrap();
+    /// assert!(heap.peek() == Some(&1));
+    /// assert!(heap.get(handle) == Some(&1));
+    /// ```
+    pub fn update<F>(&mut self, handle: MeldableHandle, modifier: F) -> Result<()>
+    where
+        F: FnOnce(&mut T),
+    {
+        match self.resolve(handle) {
+            Some(r) => {
+                self.detach(r);
+                modifier(&mut self.node_mut(r).value);
+                self.root = self.merge(self.root, Some(r));
+                self.set_parent(self.root, None);
+                Ok(())
+            }
+            None => Err(Error::new(
+                ErrorKind::InvalidHandle,
+                "Handle does not refer to an element on the heap.",
+            )),
+        }
+    }
+
+    /// Moves all the elements of `other` into `self` in expected O(log n) comparisons.
+    /// No node is copied: `self` takes over the arenas of `other`, so handles into `other`
+    /// stay valid and now refer to the same elements in `self`.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::RandomizedMeldableHeap;
+    ///
+    /// let mut a: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(3);
+    /// let mut b: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(4);
+    /// a.insert(8);
+    /// let handle = b.insert(2);
+    /// a.meld(b);
+    /// assert!(a.len() == 2);
+    /// assert!(a.get(handle) == Some(&2));
+    /// assert!(a.pop() == Some(2));
+    /// ```
+    pub fn meld(&mut self, mut other: Self) {
+        if other.arenas.len() > self.arenas.len() {
+            std::mem::swap(&mut self.arenas, &mut other.arenas);
+        }
+        self.arenas.extend(other.arenas.drain());
+        self.root = self.merge(self.root, other.root);
+        self.set_parent(self.root, None);
+        self.len += other.len;
+    }
+
+    fn resolve(&self, handle: MeldableHandle) -> Option<NodeRef> {
+        let slot: &Slot<T> = self.arenas.get(&handle.arena)?.slots.get(handle.index)?;
+        if slot.generation == handle.generation && slot.node.is_some() {
+            Some(NodeRef {
+                arena: handle.arena,
+                index: handle.index,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn node(&self, r: NodeRef) -> &Node<T> {
+        self.arenas[&r.arena].slots[r.index].node.as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, r: NodeRef) -> &mut Node<T> {
+        self.arenas.get_mut(&r.arena).unwrap().slots[r.index]
+            .node
+            .as_mut()
+            .unwrap()
+    }
+
+    fn set_parent(&mut self, child: Option<NodeRef>, parent: Option<NodeRef>) {
+        if let Some(c) = child {
+            self.node_mut(c).parent = parent;
+        }
+    }
+
+    /// Returns true if the element at `a` belongs above the element at `b`.
+    fn before(&self, a: NodeRef, b: NodeRef) -> bool {
+        O::precedes(&self.node(a).value, &self.node(b).value)
+    }
+
+    /// Detaches the node at `r` from the tree and replaces it with the meld of its children.
+    /// The detached node is left as a singleton tree with no links.
+    fn detach(&mut self, r: NodeRef) {
+        let (parent, left, right) = {
+            let n: &Node<T> = self.node(r);
+            (n.parent, n.left, n.right)
+        };
+        let subtree: Option<NodeRef> = self.merge(left, right);
+        self.set_parent(subtree, parent);
+        match parent {
+            None => self.root = subtree,
+            Some(p) => {
+                let p: &mut Node<T> = self.node_mut(p);
+                if p.left == Some(r) {
+                    p.left = subtree;
+                } else {
+                    p.right = subtree;
+                }
+            }
+        }
+        let n: &mut Node<T> = self.node_mut(r);
+        n.parent = None;
+        n.left = None;
+        n.right = None;
+    }
+
+    /// Detaches the node at `r` from the tree, frees its slot and returns its value.
+    /// An adopted arena whose last element this was is dropped.
+    fn unlink(&mut self, r: NodeRef) -> T {
+        self.detach(r);
+        self.len -= 1;
+        let arena: &mut Arena<T> = self.arenas.get_mut(&r.arena).unwrap();
+        let slot: &mut Slot<T> = &mut arena.slots[r.index];
+        slot.generation += 1;
+        let value: T = slot.node.take().unwrap().value;
+        arena.free.push(r.index);
+        arena.live -= 1;
+        if arena.live == 0 && r.arena != self.home {
+            self.arenas.remove(&r.arena);
+        }
+        value
+    }
+
+    /// Melds the trees rooted at `a` and `b` and returns the root of the result.
+    /// The caller is responsible for setting the parent link of the returned root.
+    fn merge(&mut self, a: Option<NodeRef>, b: Option<NodeRef>) -> Option<NodeRef> {
+        let (mut a, mut b) = match (a, b) {
+            (None, x) | (x, None) => return x,
+            (Some(a), Some(b)) => (a, b),
+        };
+        if self.before(b, a) {
+            std::mem::swap(&mut a, &mut b);
+        }
+        let root: NodeRef = a;
+        // `a` stays where it is; `b` is melded into a randomly chosen child of `a`.
+        loop {
+            let go_left: bool = self.rng.gen();
+            let child: Option<NodeRef> = if go_left {
+                self.node(a).left
+            } else {
+                self.node(a).right
+            };
+            let attach: bool = match child {
+                None => true,
+                Some(c) => self.before(b, c),
+            };
+            if attach {
+                if go_left {
+                    self.node_mut(a).left = Some(b);
+                } else {
+                    self.node_mut(a).right = Some(b);
+                }
+                self.node_mut(b).parent = Some(a);
+                match child {
+                    None => break,
+                    Some(c) => {
+                        // `c` must now be melded into the subtree of `b`
+                        a = b;
+                        b = c;
+                    }
+                }
+            } else {
+                a = child.unwrap();
+            }
+        }
+        Some(root)
+    }
+}
+
+impl<T, O> PriorityQueue<T> for RandomizedMeldableHeap<T, O>
+where
+    O: Order<T>,
+{
+    fn push(&mut self, element: T) {
+        self.insert(element);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        RandomizedMeldableHeap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        RandomizedMeldableHeap::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        RandomizedMeldableHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        RandomizedMeldableHeap::clear(self)
+    }
+}
+
+impl<T, O> AddressablePriorityQueue<T> for RandomizedMeldableHeap<T, O>
+where
+    O: Order<T>,
+{
+    type Handle = MeldableHandle;
+
+    fn push_with_handle(&mut self, element: T) -> MeldableHandle {
+        self.insert(element)
+    }
+
+    fn get(&self, handle: MeldableHandle) -> Option<&T> {
+        RandomizedMeldableHeap::get(self, handle)
+    }
+
+    fn update<F>(&mut self, handle: MeldableHandle, modifier: F) -> Result<MeldableHandle>
+    where
+        F: FnOnce(&mut T),
+    {
+        RandomizedMeldableHeap::update(self, handle, modifier).map(|_| handle)
+    }
+
+    fn remove(&mut self, handle: MeldableHandle) -> Result<T> {
+        RandomizedMeldableHeap::remove(self, handle)
+    }
+}
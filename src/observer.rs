@@ -0,0 +1,279 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::hook::SiftHook;
+use crate::{Heap, Order, PriorityQueue, Result};
+use std::fmt::Debug;
+
+/// Callbacks that an [`ObservedHeap`] invokes whenever an element is inserted, moved, updated or removed.
+///
+/// Every method has an empty default, so an observer only implements the events it cares about.
+/// Together the callbacks describe every position change, which is enough to keep a side table
+/// such as an element to index map in step with the heap.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{HeapObserver, Min, ObservedHeap};
+/// use std::collections::HashMap;
+///
+/// // tracks the index of every element on the heap
+/// #[derive(Debug, Default)]
+/// struct Positions(HashMap<u32, usize>);
+///
+/// impl HeapObserver<u32> for Positions {
+///     fn on_insert(&mut self, element: &u32, index: usize) {
+///         self.0.insert(*element, index);
+///     }
+///     fn on_move(&mut self, element: &u32, _from: usize, to: usize) {
+///         self.0.insert(*element, to);
+///     }
+///     fn on_remove(&mut self, element: &u32, _index: usize) {
+///         self.0.remove(element);
+///     }
+/// }
+///
+/// let mut heap: ObservedHeap<u32, Min, 2, Positions> = ObservedHeap::new(Positions::default());
+/// for n in [50, 40, 30, 20, 10] {
+///     heap.insert(n);
+/// }
+/// heap.top();
+/// for (index, n) in heap.as_heap().iter().enumerate() {
+///     assert!(heap.observer().0[n] == index);
+/// }
+/// ```
+pub trait HeapObserver<T> {
+    /// `element` was added to the heap at `index`, before being sifted into place.
+    #[inline]
+    fn on_insert(&mut self, _element: &T, _index: usize) {}
+
+    /// `element` moved from index `from` to index `to`.
+    #[inline]
+    fn on_move(&mut self, _element: &T, _from: usize, _to: usize) {}
+
+    /// `element` was removed from the heap. `index` is the position it held when it was removed.
+    #[inline]
+    fn on_remove(&mut self, _element: &T, _index: usize) {}
+
+    /// The element at `index` was changed in place from `old` to `new`, before being sifted into place.
+    #[inline]
+    fn on_update(&mut self, _old: &T, _new: &T, _index: usize) {}
+}
+
+/// Forwards the changes reported by the sift loops to an observer.
+struct Notify<'a, B>(&'a mut B);
+
+impl<T, B: HeapObserver<T>> SiftHook<T> for Notify<'_, B> {
+    #[inline]
+    fn inserted(&mut self, index: usize, element: &T) {
+        self.0.on_insert(element, index);
+    }
+
+    #[inline]
+    fn removed(&mut self, index: usize, element: &T) {
+        self.0.on_remove(element, index);
+    }
+
+    #[inline]
+    fn swapped(&mut self, heap: &[T], a: usize, b: usize) {
+        self.0.on_move(&heap[a], b, a);
+        self.0.on_move(&heap[b], a, b);
+    }
+
+    #[inline]
+    fn replaced(&mut self, heap: &[T], index: usize, old: &T) {
+        self.0.on_update(old, &heap[index], index);
+    }
+}
+
+/// A heap that reports every insertion, move, update and removal of its elements to a [`HeapObserver`].
+///
+/// The observer is called from the same sift loops that the plain [`Heap`] uses, including the
+/// rebuild performed by `append`, so it sees every position change. An element that is removed from
+/// the middle of the heap is first swapped to the end, which is reported as a move, and then removed.
+pub struct ObservedHeap<T, O, const BRANCHES: usize, B>
+where
+    T: Copy,
+    O: Order<T>,
+    B: HeapObserver<T>,
+{
+    heap: Heap<T, O, BRANCHES>,
+    observer: B,
+}
+
+impl<T, O, const BRANCHES: usize, B> Clone for ObservedHeap<T, O, BRANCHES, B>
+where
+    T: Copy,
+    O: Order<T>,
+    B: HeapObserver<T> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            observer: self.observer.clone(),
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize, B> Debug for ObservedHeap<T, O, BRANCHES, B>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+    B: HeapObserver<T> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObservedHeap")
+            .field("heap", &self.heap)
+            .field("observer", &self.observer)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize, B> ObservedHeap<T, O, BRANCHES, B>
+where
+    T: Copy,
+    O: Order<T>,
+    B: HeapObserver<T>,
+{
+    /// Constructs a new, empty heap that reports to `observer`.
+    pub fn new(observer: B) -> Self {
+        Self {
+            heap: Heap::new(),
+            observer,
+        }
+    }
+
+    /// Builds a new heap from a slice. Each element is reported as inserted at its index in the slice,
+    /// followed by the moves made by `heap_sort`.
+    pub fn from_slice(s: &[T], observer: B) -> Self {
+        let mut heap: Self = Self::new(observer);
+        heap.extend_from_slice(s);
+        heap
+    }
+
+    /// Returns the underlying heap.
+    pub fn as_heap(&self) -> &Heap<T, O, BRANCHES> {
+        &self.heap
+    }
+
+    /// Returns the observer.
+    pub fn observer(&self) -> &B {
+        &self.observer
+    }
+
+    /// Returns a mutable reference to the observer.
+    pub fn observer_mut(&mut self) -> &mut B {
+        &mut self.observer
+    }
+
+    /// Consumes the wrapper and returns the heap and the observer.
+    pub fn into_parts(self) -> (Heap<T, O, BRANCHES>, B) {
+        (self.heap, self.observer)
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns an immutable reference to the element on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Inserts an element into the heap and returns its index.
+    pub fn insert(&mut self, element: T) -> usize {
+        self.heap
+            .insert_hooked(element, &mut Notify(&mut self.observer))
+    }
+
+    /// Removes and returns the element from the top of the heap. Returns `None` if the heap is empty.
+    pub fn top(&mut self) -> Option<T> {
+        if self.heap.is_empty() {
+            None
+        } else {
+            Some(self.heap.remove_hooked(0, &mut Notify(&mut self.observer)))
+        }
+    }
+
+    /// Removes and returns the element at `index`.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<T> {
+        self.heap.check_index(index)?;
+        Ok(self
+            .heap
+            .remove_hooked(index, &mut Notify(&mut self.observer)))
+    }
+
+    /// Updates the value of the element at `index` and returns its new index.
+    /// The change is reported with `on_update` before the element is sifted into place.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn update<F>(&mut self, index: usize, modifier: F) -> Result<usize>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.heap.check_index(index)?;
+        let old: T = self.heap.heap[index];
+        modifier(&mut self.heap.heap[index]);
+        let mut notify: Notify<'_, B> = Notify(&mut self.observer);
+        notify.replaced(&self.heap.heap, index, &old);
+        Ok(self.heap.restore_hooked(index, &mut notify))
+    }
+
+    /// Moves all the elements of other into self, leaving other empty.
+    /// Each moved element is reported as inserted at the end of this heap, followed by the moves made by `heap_sort`.
+    pub fn append<const D2: usize>(&mut self, other: &mut Heap<T, O, D2>) {
+        let moved: Vec<T> = std::mem::take(&mut other.heap);
+        self.extend_from_slice(&moved);
+    }
+
+    /// Removes every element from the heap, reporting each removal from the last index to the first.
+    pub fn clear(&mut self) {
+        while let Some(element) = self.heap.heap.pop() {
+            self.observer.on_remove(&element, self.heap.heap.len());
+        }
+    }
+
+    fn extend_from_slice(&mut self, s: &[T]) {
+        let mut notify: Notify<'_, B> = Notify(&mut self.observer);
+        for element in s.iter() {
+            let index: usize = self.heap.heap.len();
+            self.heap.heap.push(*element);
+            notify.inserted(index, element);
+        }
+        Heap::<T, O, BRANCHES>::heap_sort_hooked(&mut self.heap.heap, &mut notify);
+    }
+}
+
+impl<T, O, const BRANCHES: usize, B> PriorityQueue<T> for ObservedHeap<T, O, BRANCHES, B>
+where
+    T: Copy,
+    O: Order<T>,
+    B: HeapObserver<T>,
+{
+    fn push(&mut self, element: T) {
+        self.insert(element);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        ObservedHeap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.top()
+    }
+
+    fn len(&self) -> usize {
+        ObservedHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        ObservedHeap::clear(self)
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use std::cmp::{Ord, Ordering};
+
+/// A type-level ordering policy that decides which of two elements belongs closer to the top of a heap.
+///
+/// [`Min`] and [`Max`] cover the common cases. Implement this trait on your own zero-sized
+/// type to order by a field, by the `Reverse` of a field, or by a total order over types
+/// that are not `Ord`, such as floats.
+///
+/// The searches that skip subtrees, such as [`crate::Heap::find`] and [`crate::Heap::contains`],
+/// require `PartialEq` to agree with the policy: `x == y` must imply `compare(x, y) == Ordering::Equal`.
+/// If equality looks at fewer fields than the policy, search with `iter().position` instead.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{Heap, Order};
+/// use std::cmp::{Ordering, Reverse};
+///
+/// #[derive(Debug, Clone, Copy)]
+/// struct Job {
+///     deadline: u32,
+///     id: u32,
+/// }
+///
+/// // The job with the latest deadline goes on top.
+/// struct LatestDeadline;
+///
+/// impl Order<Job> for LatestDeadline {
+///     fn compare(a: &Job, b: &Job) -> Ordering {
+///         Reverse(a.deadline).cmp(&Reverse(b.deadline))
+///     }
+/// }
+///
+/// let mut heap: Heap<Job, LatestDeadline, 2> = Heap::new();
+/// heap.insert(Job { deadline: 5, id: 1 });
+/// heap.insert(Job { deadline: 9, id: 2 });
+/// heap.insert(Job { deadline: 7, id: 3 });
+/// assert!(heap.clone().top().unwrap().id == 2);
+/// assert!(format!("{:?}", heap).contains("deadline: 9"));
+/// ```
+pub trait Order<T: ?Sized> {
+    /// Compares `a` with `b`.
+    /// `Ordering::Less` means `a` belongs closer to the top of the heap than `b`.
+    fn compare(a: &T, b: &T) -> Ordering;
+
+    /// Returns true if `a` belongs strictly closer to the top of the heap than `b`.
+    #[inline]
+    fn precedes(a: &T, b: &T) -> bool {
+        Self::compare(a, b) == Ordering::Less
+    }
+}
+
+/// An ordering policy that has an exact opposite, used by [`crate::Heap::into_reversed`].
+pub trait ReverseOrder<T: ?Sized>: Order<T> {
+    /// The policy that puts the element on top of the heap that this policy puts at the bottom.
+    type Reverse: Order<T>;
+}
+
+/// The ordering policy of a minimum heap. The smallest element is on top.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Min;
+
+/// The ordering policy of a maximum heap. The largest element is on top.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Max;
+
+impl<T: Ord + ?Sized> Order<T> for Min {
+    #[inline]
+    fn compare(a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<T: Ord + ?Sized> Order<T> for Max {
+    #[inline]
+    fn compare(a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<T: Ord + ?Sized> ReverseOrder<T> for Min {
+    type Reverse = Max;
+}
+
+impl<T: Ord + ?Sized> ReverseOrder<T> for Max {
+    type Reverse = Min;
+}
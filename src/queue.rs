@@ -0,0 +1,86 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::Result;
+
+/// The operations shared by the priority queues in this crate.
+/// Code written against this trait can be reused with any heap structure or arity.
+///
+/// [`crate::IndexHeap`] and [`crate::FloatHeap`] do not implement it because their `insert` can fail,
+/// and [`crate::LazyHeap`] does not because its `peek` needs `&mut self` to discard cancelled elements.
+/// A [`crate::SetHeap`] silently drops a pushed element that is already on the heap.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{BinaryMinHeap, PriorityQueue, QuaternaryMinHeap, RandomizedMeldableHeap};
+///
+/// fn drain_sorted<Q: PriorityQueue<usize>>(queue: &mut Q) -> Vec<usize> {
+///     let mut v: Vec<usize> = Vec::new();
+///     while let Some(x) = queue.pop() {
+///         v.push(x);
+///     }
+///     v
+/// }
+///
+/// let mut a: BinaryMinHeap<usize> = BinaryMinHeap::new();
+/// let mut b: QuaternaryMinHeap<usize> = QuaternaryMinHeap::new();
+/// let mut c: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(0);
+/// for x in [4, 1, 3] {
+///     a.push(x);
+///     b.push(x);
+///     c.push(x);
+/// }
+/// assert!(drain_sorted(&mut a) == vec![1, 3, 4]);
+/// assert!(drain_sorted(&mut b) == vec![1, 3, 4]);
+/// assert!(drain_sorted(&mut c) == vec![1, 3, 4]);
+/// ```
+pub trait PriorityQueue<T> {
+    /// Inserts an element into the queue.
+    fn push(&mut self, element: T);
+
+    /// Returns an immutable reference to the element with the highest priority without removing it or `None` if the queue is empty.
+    fn peek(&self) -> Option<&T>;
+
+    /// Removes and returns the element with the highest priority. Returns `None` if the queue is empty.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Returns the number of elements in the queue.
+    fn len(&self) -> usize;
+
+    /// Returns true if the queue contains no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all elements from the queue.
+    fn clear(&mut self);
+}
+
+/// A priority queue whose elements can be addressed through handles after they have been inserted.
+///
+/// A handle must stay valid until its element is removed, whatever else happens to the queue,
+/// and a stale handle must be rejected rather than refer to another element. Array based heaps
+/// such as [`crate::Heap`] move their elements on every mutation, so they do not implement this trait;
+/// use [`crate::RandomizedMeldableHeap`] instead.
+pub trait AddressablePriorityQueue<T>: PriorityQueue<T> {
+    /// The type used to address an element in the queue.
+    type Handle: Copy;
+
+    /// Inserts an element into the queue and returns its handle.
+    fn push_with_handle(&mut self, element: T) -> Self::Handle;
+
+    /// Returns an immutable reference to the element referred to by `handle`, or `None` if the handle is not valid.
+    fn get(&self, handle: Self::Handle) -> Option<&T>;
+
+    /// Modifies the element referred to by `handle`, restores the heap property and returns the element's new handle.
+    /// Returns an error if the handle is not valid.
+    fn update<F>(&mut self, handle: Self::Handle, modifier: F) -> Result<Self::Handle>
+    where
+        F: FnOnce(&mut T);
+
+    /// Removes and returns the element referred to by `handle`.
+    /// Returns an error if the handle is not valid.
+    fn remove(&mut self, handle: Self::Handle) -> Result<T>;
+}
@@ -0,0 +1,183 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Heap, Order};
+use std::fmt::{Debug, Display, Formatter, Write};
+
+impl<T, O, const BRANCHES: usize> Heap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    /// Returns the indices of every element that belongs strictly closer to the top of the heap than its parent.
+    /// The list is empty for a valid heap. It can be passed to [`Heap::to_dot`] to highlight the offending nodes.
+    pub fn violations(&self) -> Vec<usize> {
+        (1..self.heap.len())
+            .filter(|&i| O::precedes(&self.heap[i], &self.heap[(i - 1) / BRANCHES]))
+            .collect()
+    }
+
+    /// Returns the heap's tree in the Graphviz DOT language, with the nodes at the indices in `highlight` filled in red.
+    /// Each node is labelled with its element and named after its index. Indices that are out of bounds are ignored.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let heap: Heap<u32, Min, 2> = Heap::from(&[1, 2, 3][..]);
+    /// let dot: String = heap.to_dot(&[2]);
+    /// assert!(dot.starts_with("digraph heap {"));
+    /// assert!(dot.contains("n2 [label=\"3\", style=filled, fillcolor=red];"));
+    /// assert!(dot.contains("n0 -> n1;") && dot.contains("n0 -> n2;"));
+    /// ```
+    pub fn to_dot(&self, highlight: &[usize]) -> String
+    where
+        T: Display,
+    {
+        let mut dot: String = String::from("digraph heap {\n    node [shape=circle];\n");
+        for (i, x) in self.heap.iter().enumerate() {
+            let label: String = x.to_string().replace('\\', "\\\\").replace('"', "\\\"");
+            if highlight.contains(&i) {
+                writeln!(
+                    dot,
+                    "    n{} [label=\"{}\", style=filled, fillcolor=red];",
+                    i, label
+                )
+                .unwrap();
+            } else {
+                writeln!(dot, "    n{} [label=\"{}\"];", i, label).unwrap();
+            }
+        }
+        for i in 1..self.heap.len() {
+            writeln!(dot, "    n{} -> n{};", (i - 1) / BRANCHES, i).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Returns a wrapper that renders the heap as an ASCII tree showing at most `depth` levels,
+    /// like the heap's own `Display` does without a limit. Deeper subtrees are elided as `...`.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let heap: Heap<u32, Min, 2> = Heap::from(&[1, 2, 3, 4, 5][..]);
+    /// assert!(heap.display_depth(2).to_string() == "1\n|-- 2\n|   `-- ...\n`-- 3\n");
+    /// ```
+    pub fn display_depth(&self, depth: usize) -> DisplayDepth<'_, T, O, BRANCHES> {
+        DisplayDepth { heap: self, depth }
+    }
+
+    /// Writes the heap as an ASCII tree of at most `max_depth` levels.
+    /// Every element is formatted with the flags of `f`, so `{:.2}` applies to each element.
+    fn write_root(&self, f: &mut Formatter<'_>, max_depth: usize) -> std::fmt::Result
+    where
+        T: Display,
+    {
+        if self.heap.is_empty() || max_depth == 0 {
+            return Ok(());
+        }
+        Display::fmt(&self.heap[0], f)?;
+        f.write_char('\n')?;
+        self.write_tree(f, 0, &mut String::new(), 0, max_depth)
+    }
+
+    /// Writes the subtree rooted at `index` as lines of an ASCII tree.
+    fn write_tree(
+        &self,
+        f: &mut Formatter<'_>,
+        index: usize,
+        prefix: &mut String,
+        depth: usize,
+        max_depth: usize,
+    ) -> std::fmt::Result
+    where
+        T: Display,
+    {
+        let children: std::ops::Range<usize> = self.children(index);
+        if children.is_empty() {
+            return Ok(());
+        }
+        if depth + 1 >= max_depth {
+            return writeln!(f, "{}`-- ...", prefix);
+        }
+        let last: usize = children.end - 1;
+        for child in children {
+            let (branch, indent) = if child == last {
+                ("`-- ", "    ")
+            } else {
+                ("|-- ", "|   ")
+            };
+            f.write_str(prefix)?;
+            f.write_str(branch)?;
+            Display::fmt(&self.heap[child], f)?;
+            f.write_char('\n')?;
+            prefix.push_str(indent);
+            self.write_tree(f, child, prefix, depth + 1, max_depth)?;
+            prefix.truncate(prefix.len() - indent.len());
+        }
+        Ok(())
+    }
+}
+
+/// Renders a [`Heap`] as an ASCII tree limited to a number of levels.
+///
+/// This struct is created by [`Heap::display_depth`].
+pub struct DisplayDepth<'a, T, O, const BRANCHES: usize>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    heap: &'a Heap<T, O, BRANCHES>,
+    depth: usize,
+}
+
+impl<'a, T, O, const BRANCHES: usize> Debug for DisplayDepth<'a, T, O, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DisplayDepth")
+            .field("heap", &self.heap)
+            .field("depth", &self.depth)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Display for DisplayDepth<'_, T, O, BRANCHES>
+where
+    T: Copy + Display,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.heap.write_root(f, self.depth)
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Display for Heap<T, O, BRANCHES>
+where
+    T: Copy + Display,
+    O: Order<T>,
+{
+    /// Renders the heap as an ASCII tree, one element per line, with the root on the first line.
+    /// The formatting flags apply to each element, so `{:.1}` prints every float with one decimal.
+    /// Use [`Heap::display_depth`] to limit the number of levels shown.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use rheap::{Heap, Min};
+    ///
+    /// let heap: Heap<u32, Min, 2> = Heap::from(&[1, 2, 3, 4, 5][..]);
+    /// assert!(heap.to_string() == "1\n|-- 2\n|   |-- 4\n|   `-- 5\n`-- 3\n");
+    /// assert!(format!("{:03}", heap) == "001\n|-- 002\n|   |-- 004\n|   `-- 005\n`-- 003\n");
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_root(f, usize::MAX)
+    }
+}
@@ -0,0 +1,367 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Heap, Order, PriorityQueue};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// A heap that holds each value at most once.
+///
+/// Membership is tracked in a `HashSet` next to the heap, so `insert` and `contains` run in O(1)
+/// expected time on top of the usual sift. Two values are duplicates if they are equal by `Eq`,
+/// regardless of how the ordering policy `O` compares them. As a [`PriorityQueue`], `push` ignores duplicates.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{Min, SetHeap};
+///
+/// let mut heap: SetHeap<u32, Min, 2> = SetHeap::new();
+/// assert!(heap.insert(7));
+/// assert!(heap.insert(3));
+/// assert!(!heap.insert(7));
+/// assert!(heap.len() == 2);
+/// assert!(heap.top() == Some(3));
+/// assert!(heap.insert(3));
+/// ```
+pub struct SetHeap<T, O, const BRANCHES: usize>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    heap: Heap<T, O, BRANCHES>,
+    members: HashSet<T>,
+}
+
+impl<T, O, const BRANCHES: usize> Clone for SetHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            members: self.members.clone(),
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Debug for SetHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetHeap")
+            .field("heap", &self.heap)
+            .field("members", &self.members)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Default for SetHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> SetHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    /// Constructs a new, empty heap.
+    pub fn new() -> Self {
+        Self {
+            heap: Heap::new(),
+            members: HashSet::new(),
+        }
+    }
+
+    /// Constructs a new, empty heap with at least the specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            heap: Heap::with_capacity(capacity),
+            members: HashSet::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the underlying heap.
+    pub fn as_heap(&self) -> &Heap<T, O, BRANCHES> {
+        &self.heap
+    }
+
+    /// Returns the number of values in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the heap contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Clears the heap, removing all values.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.members.clear();
+    }
+
+    /// Returns true if `value` is on the heap. Runs in O(1) expected time.
+    pub fn contains(&self, value: &T) -> bool {
+        self.members.contains(value)
+    }
+
+    /// Returns an immutable reference to the value on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Inserts `value` into the heap if it is not already there.
+    /// Returns true if the value was new and false if it was already on the heap.
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.members.insert(value) {
+            self.heap.insert(value);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes and returns the value from the top of the heap. Returns `None` if the heap is empty.
+    pub fn top(&mut self) -> Option<T> {
+        let value: T = self.heap.top()?;
+        self.members.remove(&value);
+        Some(value)
+    }
+
+    /// Removes `value` from the heap. Returns true if the value was on the heap.
+    /// The value is located by a linear scan using `Eq`, so it is found even when `Eq` is coarser than `O`.
+    pub fn remove(&mut self, value: &T) -> bool {
+        match self.heap.iter().position(|x| x == value) {
+            Some(index) => {
+                self.heap.remove(index).unwrap();
+                self.members.remove(value);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A heap that stores each distinct value once together with the number of times it was inserted.
+///
+/// Counts are kept in a `HashMap` next to a heap of distinct values, so `count` runs in O(1)
+/// expected time and inserting a repeated value does not touch the heap at all.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{CountingHeap, Max};
+///
+/// let mut heap: CountingHeap<u8, Max, 4> = CountingHeap::new();
+/// for n in [3, 9, 3, 3, 1] {
+///     heap.insert(n);
+/// }
+/// assert!(heap.len() == 5 && heap.distinct_len() == 3);
+/// assert!(heap.count(&3) == 3);
+/// assert!(heap.top() == Some(9));
+/// assert!(heap.top() == Some(3));
+/// assert!(heap.count(&3) == 2);
+/// ```
+pub struct CountingHeap<T, O, const BRANCHES: usize>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    heap: Heap<T, O, BRANCHES>,
+    counts: HashMap<T, usize>,
+    len: usize,
+}
+
+impl<T, O, const BRANCHES: usize> Clone for CountingHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            counts: self.counts.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Debug for CountingHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CountingHeap")
+            .field("heap", &self.heap)
+            .field("counts", &self.counts)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Default for CountingHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> CountingHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    /// Constructs a new, empty heap.
+    pub fn new() -> Self {
+        Self {
+            heap: Heap::new(),
+            counts: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of values in the heap, counting every copy.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of distinct values in the heap.
+    pub fn distinct_len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the heap contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clears the heap, removing all values.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.counts.clear();
+        self.len = 0;
+    }
+
+    /// Returns the number of copies of `value` on the heap. Runs in O(1) expected time.
+    pub fn count(&self, value: &T) -> usize {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    /// Returns an iterator over the distinct values and their counts, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> + '_ {
+        self.heap.iter().map(|v| (v, self.counts[v]))
+    }
+
+    /// Returns an immutable reference to the value on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Inserts one copy of `value` and returns the number of copies now on the heap.
+    pub fn insert(&mut self, value: T) -> usize {
+        self.len += 1;
+        let count: &mut usize = self.counts.entry(value).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            self.heap.insert(value);
+        }
+        *count
+    }
+
+    /// Removes one copy of the value on top of the heap and returns it. Returns `None` if the heap is empty.
+    /// The value leaves the underlying heap only when its last copy is removed.
+    pub fn top(&mut self) -> Option<T> {
+        let value: T = *self.heap.peek()?;
+        let count: &mut usize = self.counts.get_mut(&value).unwrap();
+        *count -= 1;
+        if *count == 0 {
+            self.counts.remove(&value);
+            self.heap.top();
+        }
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Removes every copy of `value` and returns how many there were.
+    /// The value is located by a linear scan using `Eq`, so it is found even when `Eq` is coarser than `O`.
+    pub fn remove_all(&mut self, value: &T) -> usize {
+        match self.heap.iter().position(|x| x == value) {
+            Some(index) => {
+                self.heap.remove(index).unwrap();
+                let count: usize = self.counts.remove(value).unwrap();
+                self.len -= count;
+                count
+            }
+            None => 0,
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize> PriorityQueue<T> for CountingHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    fn push(&mut self, element: T) {
+        self.insert(element);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        CountingHeap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.top()
+    }
+
+    fn len(&self) -> usize {
+        CountingHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        CountingHeap::clear(self)
+    }
+}
+
+impl<T, O, const BRANCHES: usize> PriorityQueue<T> for SetHeap<T, O, BRANCHES>
+where
+    T: Copy + Eq + Hash,
+    O: Order<T>,
+{
+    fn push(&mut self, element: T) {
+        self.insert(element);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        SetHeap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.top()
+    }
+
+    fn len(&self) -> usize {
+        SetHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        SetHeap::clear(self)
+    }
+}
@@ -0,0 +1,351 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Heap, Order, PriorityQueue, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// Decides which of two equal elements on a [`StableHeap`] goes first, based on the stamps they were given on insertion.
+pub trait TieBreak {
+    /// True if stamps are drawn from a seeded random number generator instead of a counter.
+    const RANDOM: bool;
+
+    /// Compares the stamps of two equal elements.
+    /// `Ordering::Less` means the element stamped `a` belongs closer to the top of the heap.
+    fn compare(a: u64, b: u64) -> Ordering;
+}
+
+/// Equal elements leave the heap in the order they were inserted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Fifo;
+
+/// Equal elements leave the heap in the reverse of the order they were inserted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Lifo;
+
+/// Equal elements leave the heap in a random order that is reproducible for a given seed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Random;
+
+impl TieBreak for Fifo {
+    const RANDOM: bool = false;
+
+    #[inline]
+    fn compare(a: u64, b: u64) -> Ordering {
+        a.cmp(&b)
+    }
+}
+
+impl TieBreak for Lifo {
+    const RANDOM: bool = false;
+
+    #[inline]
+    fn compare(a: u64, b: u64) -> Ordering {
+        b.cmp(&a)
+    }
+}
+
+impl TieBreak for Random {
+    const RANDOM: bool = true;
+
+    #[inline]
+    fn compare(a: u64, b: u64) -> Ordering {
+        a.cmp(&b)
+    }
+}
+
+/// An element together with the stamp it was given on insertion.
+#[derive(Debug, Clone, Copy)]
+struct Stamped<T> {
+    value: T,
+    stamp: u64,
+}
+
+/// Orders stamped elements by `O` and breaks ties with `B`.
+#[derive(Debug, Clone)]
+struct Stable<O, B>(PhantomData<(O, B)>);
+
+impl<T, O, B> Order<Stamped<T>> for Stable<O, B>
+where
+    O: Order<T>,
+    B: TieBreak,
+{
+    #[inline]
+    fn compare(a: &Stamped<T>, b: &Stamped<T>) -> Ordering {
+        O::compare(&a.value, &b.value).then_with(|| B::compare(a.stamp, b.stamp))
+    }
+}
+
+/// A heap that breaks ties between equal elements according to the policy `B`, which is [`Fifo`] by default.
+///
+/// Every inserted element is stamped with a monotonically increasing sequence number, or with
+/// a seeded random number for the [`Random`] policy. The stamps are kept next to the elements,
+/// so `T` does not need a sequence field of its own. An element keeps its stamp when it is updated.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{Fifo, Order, StableHeap};
+/// use std::cmp::Ordering;
+///
+/// // (priority, job id), ordered by priority only
+/// #[derive(Debug, Clone, Copy)]
+/// struct Job(u8, u32);
+///
+/// #[derive(Debug, Clone)]
+/// struct ByPriority;
+///
+/// impl Order<Job> for ByPriority {
+///     fn compare(a: &Job, b: &Job) -> Ordering {
+///         b.0.cmp(&a.0)
+///     }
+/// }
+///
+/// let mut heap: StableHeap<Job, ByPriority, 2, Fifo> = StableHeap::new();
+/// for (id, priority) in [1, 5, 1, 5, 1].into_iter().enumerate() {
+///     heap.insert(Job(priority, id as u32));
+/// }
+/// let order: Vec<u32> = std::iter::from_fn(|| heap.top()).map(|job| job.1).collect();
+/// assert!(order == vec![1, 3, 0, 2, 4]);
+/// ```
+pub struct StableHeap<T, O, const BRANCHES: usize, B = Fifo>
+where
+    T: Copy,
+    O: Order<T>,
+    B: TieBreak,
+{
+    heap: Heap<Stamped<T>, Stable<O, B>, BRANCHES>,
+    sequence: u64,
+    rng: Option<StdRng>,
+}
+
+impl<T, O, const BRANCHES: usize, B> Clone for StableHeap<T, O, BRANCHES, B>
+where
+    T: Copy,
+    O: Order<T>,
+    B: TieBreak,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            sequence: self.sequence,
+            rng: self.rng.clone(),
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize, B> Debug for StableHeap<T, O, BRANCHES, B>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+    B: TieBreak,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StableHeap")
+            .field("heap", &self.heap)
+            .field("sequence", &self.sequence)
+            .field("rng", &self.rng)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize, B> Default for StableHeap<T, O, BRANCHES, B>
+where
+    T: Copy,
+    O: Order<T>,
+    B: TieBreak,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O, const BRANCHES: usize, B> From<&[T]> for StableHeap<T, O, BRANCHES, B>
+where
+    T: Copy,
+    O: Order<T>,
+    B: TieBreak,
+{
+    /// Builds a new heap from a slice, stamping the elements in slice order.
+    fn from(s: &[T]) -> Self {
+        let mut heap: Self = Self::with_capacity(s.len());
+        for value in s.iter() {
+            let stamp: u64 = heap.next_stamp();
+            heap.heap.heap.push(Stamped {
+                value: *value,
+                stamp,
+            });
+        }
+        Heap::<Stamped<T>, Stable<O, B>, BRANCHES>::heap_sort(&mut heap.heap.heap);
+        heap
+    }
+}
+
+impl<T, O, const BRANCHES: usize> StableHeap<T, O, BRANCHES, Random>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    /// Constructs a new, empty heap whose ties are broken by a random number generator seeded with `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            heap: Heap::new(),
+            sequence: 0,
+            rng: Some(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize, B> StableHeap<T, O, BRANCHES, B>
+where
+    T: Copy,
+    O: Order<T>,
+    B: TieBreak,
+{
+    /// Constructs a new, empty heap.
+    /// With the [`Random`] policy, the random number generator is seeded from system entropy.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Constructs a new, empty heap with at least the specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            heap: Heap::with_capacity(capacity),
+            sequence: 0,
+            rng: if B::RANDOM {
+                Some(StdRng::from_entropy())
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Clears the heap, removing all elements.
+    /// The sequence counter is not reset, so elements inserted later still follow the ones removed.
+    pub fn clear(&mut self) {
+        self.heap.clear()
+    }
+
+    /// Returns an iterator over the elements in the order they are stored in the underlying vector.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.heap.iter().map(|s| &s.value)
+    }
+
+    /// Returns an immutable reference to the element on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|s| &s.value)
+    }
+
+    /// Inserts an element into the heap, stamping it after every element inserted before it.
+    pub fn insert(&mut self, element: T) {
+        let stamp: u64 = self.next_stamp();
+        self.heap.insert(Stamped {
+            value: element,
+            stamp,
+        });
+    }
+
+    /// Removes and returns the element from the top of the heap. Returns `None` if the heap is empty.
+    pub fn top(&mut self) -> Option<T> {
+        self.heap.top().map(|s| s.value)
+    }
+
+    /// Removes and returns the element at `index`.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<T> {
+        self.heap.remove(index).map(|s| s.value)
+    }
+
+    /// Updates the value of the element at `index` and returns its new index.
+    /// The element keeps its stamp, so it keeps its place among the elements it is tied with.
+    /// Returns an error if the index is out of bounds.
+    pub fn update<F>(&mut self, index: usize, modifier: F) -> Result<usize>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.heap
+            .update_with(index, |s| modifier(&mut s.value))
+            .map(|(index, _)| index)
+    }
+
+    /// Moves all the elements of other into self, leaving other empty.
+    /// The moved elements are stamped again, in the order they were inserted into `other`,
+    /// so they follow every element already on this heap.
+    pub fn append(&mut self, other: &mut Self) {
+        let mut moved: Vec<Stamped<T>> = std::mem::take(&mut other.heap.heap);
+        moved.sort_by_key(|s| s.stamp);
+        for s in moved.iter_mut() {
+            s.stamp = self.next_stamp();
+        }
+        self.heap.heap.append(&mut moved);
+        Heap::<Stamped<T>, Stable<O, B>, BRANCHES>::heap_sort(&mut self.heap.heap);
+    }
+
+    /// Performs a linear search (in O(n) time) to find the index of an element on the heap.
+    pub fn find(&self, element: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.heap.iter().position(|s| s.value == *element)
+    }
+
+    /// Returns true if the heap property, including the tie-breaking order, holds.
+    /// Please note that this function is intended for use during testing.
+    #[doc(hidden)]
+    pub fn is_valid(&self) -> bool {
+        let s: &[Stamped<T>] = self.heap.as_slice();
+        (1..s.len()).all(|i| !Stable::<O, B>::precedes(&s[i], &s[(i - 1) / BRANCHES]))
+    }
+
+    fn next_stamp(&mut self) -> u64 {
+        match self.rng.as_mut() {
+            Some(rng) => rng.gen(),
+            None => {
+                self.sequence += 1;
+                self.sequence
+            }
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize, B> PriorityQueue<T> for StableHeap<T, O, BRANCHES, B>
+where
+    T: Copy,
+    O: Order<T>,
+    B: TieBreak,
+{
+    fn push(&mut self, element: T) {
+        self.insert(element)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        StableHeap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.top()
+    }
+
+    fn len(&self) -> usize {
+        StableHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        StableHeap::clear(self)
+    }
+}
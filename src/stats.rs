@@ -0,0 +1,315 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::hook::SiftHook;
+use crate::{Heap, Order, PriorityQueue, Result};
+use std::fmt::Debug;
+
+/// The work done by one type of operation on an [`InstrumentedHeap`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpStats {
+    /// The number of calls.
+    pub calls: u64,
+    /// The number of comparisons made by the ordering policy.
+    pub comparisons: u64,
+    /// The number of element swaps.
+    pub swaps: u64,
+    /// The number of levels that sifted elements travelled up or down the tree.
+    pub levels: u64,
+}
+
+impl OpStats {
+    /// Returns the average number of comparisons per call, or zero if there were no calls.
+    pub fn comparisons_per_call(&self) -> f64 {
+        Self::per_call(self.comparisons, self.calls)
+    }
+
+    /// Returns the average number of swaps per call, or zero if there were no calls.
+    pub fn swaps_per_call(&self) -> f64 {
+        Self::per_call(self.swaps, self.calls)
+    }
+
+    /// Returns the average number of levels traversed per call, or zero if there were no calls.
+    pub fn levels_per_call(&self) -> f64 {
+        Self::per_call(self.levels, self.calls)
+    }
+
+    fn per_call(count: u64, calls: u64) -> f64 {
+        if calls == 0 {
+            0.0
+        } else {
+            count as f64 / calls as f64
+        }
+    }
+}
+
+/// Counts the work reported by the sift loops into an [`OpStats`].
+struct Counter<'a>(&'a mut OpStats);
+
+impl<T> SiftHook<T> for Counter<'_> {
+    #[inline]
+    fn swapped(&mut self, _heap: &[T], _a: usize, _b: usize) {
+        self.0.swaps += 1;
+    }
+
+    #[inline]
+    fn compared(&mut self) {
+        self.0.comparisons += 1;
+    }
+
+    #[inline]
+    fn traversed(&mut self) {
+        self.0.levels += 1;
+    }
+}
+
+/// A snapshot of the work done by an [`InstrumentedHeap`], broken down by operation type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Calls to `insert`.
+    pub insert: OpStats,
+    /// Calls to `top`.
+    pub top: OpStats,
+    /// Calls to `remove`.
+    pub remove: OpStats,
+    /// Calls to `update`.
+    pub update: OpStats,
+    /// Rebuilds with `heap_sort`, made by `from_slice` and `append`.
+    pub rebuild: OpStats,
+}
+
+impl HeapStats {
+    /// Returns the sum of the work done by every type of operation.
+    pub fn total(&self) -> OpStats {
+        [
+            self.insert,
+            self.top,
+            self.remove,
+            self.update,
+            self.rebuild,
+        ]
+        .iter()
+        .fold(OpStats::default(), |a, b| OpStats {
+            calls: a.calls + b.calls,
+            comparisons: a.comparisons + b.comparisons,
+            swaps: a.swaps + b.swaps,
+            levels: a.levels + b.levels,
+        })
+    }
+}
+
+/// A heap that counts the comparisons, swaps and levels traversed by each type of operation.
+///
+/// The counts are gathered inside the same sift loops that the plain [`Heap`] uses, so they
+/// reflect the real cost of a workload for a given number of branches.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{HeapStats, InstrumentedHeap, Min};
+///
+/// let mut binary: InstrumentedHeap<u32, Min, 2> = InstrumentedHeap::new();
+/// let mut octal: InstrumentedHeap<u32, Min, 8> = InstrumentedHeap::new();
+/// for n in (0..1000).rev() {
+///     binary.insert(n);
+///     octal.insert(n);
+/// }
+/// let (b, o): (HeapStats, HeapStats) = (binary.stats(), octal.stats());
+/// assert!(b.insert.calls == 1000 && o.insert.calls == 1000);
+/// assert!(o.insert.levels < b.insert.levels);
+///
+/// binary.reset_stats();
+/// assert!(binary.stats() == HeapStats::default());
+/// ```
+pub struct InstrumentedHeap<T, O, const BRANCHES: usize>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    heap: Heap<T, O, BRANCHES>,
+    stats: HeapStats,
+}
+
+impl<T, O, const BRANCHES: usize> Clone for InstrumentedHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            stats: self.stats,
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Debug for InstrumentedHeap<T, O, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InstrumentedHeap")
+            .field("heap", &self.heap)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Default for InstrumentedHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> InstrumentedHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    /// Constructs a new, empty heap with all counters at zero.
+    pub fn new() -> Self {
+        Self {
+            heap: Heap::new(),
+            stats: HeapStats::default(),
+        }
+    }
+
+    /// Builds a new heap from a slice. The rebuild is counted under [`HeapStats::rebuild`].
+    pub fn from_slice(s: &[T]) -> Self {
+        let mut heap: Self = Self::new();
+        heap.heap.heap.extend_from_slice(s);
+        heap.rebuild();
+        heap
+    }
+
+    /// Returns the underlying heap.
+    pub fn as_heap(&self) -> &Heap<T, O, BRANCHES> {
+        &self.heap
+    }
+
+    /// Consumes the wrapper and returns the underlying heap.
+    pub fn into_heap(self) -> Heap<T, O, BRANCHES> {
+        self.heap
+    }
+
+    /// Returns a snapshot of the counters.
+    pub fn stats(&self) -> HeapStats {
+        self.stats
+    }
+
+    /// Sets every counter back to zero.
+    pub fn reset_stats(&mut self) {
+        self.stats = HeapStats::default();
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns an immutable reference to the element on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Inserts an element into the heap and returns its index.
+    pub fn insert(&mut self, element: T) -> usize {
+        self.stats.insert.calls += 1;
+        self.heap
+            .insert_hooked(element, &mut Counter(&mut self.stats.insert))
+    }
+
+    /// Removes and returns the element from the top of the heap. Returns `None` if the heap is empty.
+    pub fn top(&mut self) -> Option<T> {
+        self.stats.top.calls += 1;
+        if self.heap.is_empty() {
+            None
+        } else {
+            Some(
+                self.heap
+                    .remove_hooked(0, &mut Counter(&mut self.stats.top)),
+            )
+        }
+    }
+
+    /// Removes and returns the element at `index`.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<T> {
+        self.stats.remove.calls += 1;
+        self.heap.check_index(index)?;
+        Ok(self
+            .heap
+            .remove_hooked(index, &mut Counter(&mut self.stats.remove)))
+    }
+
+    /// Updates the value of the element at `index` and returns its new index.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn update<F>(&mut self, index: usize, modifier: F) -> Result<usize>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.stats.update.calls += 1;
+        self.heap.check_index(index)?;
+        modifier(&mut self.heap.heap[index]);
+        Ok(self
+            .heap
+            .restore_hooked(index, &mut Counter(&mut self.stats.update)))
+    }
+
+    /// Moves all the elements of other into self, leaving other empty.
+    /// The rebuild is counted under [`HeapStats::rebuild`].
+    pub fn append<const D2: usize>(&mut self, other: &mut Heap<T, O, D2>) {
+        self.heap.heap.append(&mut other.heap);
+        self.rebuild();
+    }
+
+    /// Clears the heap, removing all elements. The counters are left as they are.
+    pub fn clear(&mut self) {
+        self.heap.clear()
+    }
+
+    fn rebuild(&mut self) {
+        self.stats.rebuild.calls += 1;
+        Heap::<T, O, BRANCHES>::heap_sort_hooked(
+            &mut self.heap.heap,
+            &mut Counter(&mut self.stats.rebuild),
+        );
+    }
+}
+
+impl<T, O, const BRANCHES: usize> PriorityQueue<T> for InstrumentedHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn push(&mut self, element: T) {
+        self.insert(element);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        InstrumentedHeap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.top()
+    }
+
+    fn len(&self) -> usize {
+        InstrumentedHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        InstrumentedHeap::clear(self)
+    }
+}
@@ -0,0 +1,337 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::hook::SiftHook;
+use crate::{Error, ErrorKind, Heap, Order, PriorityQueue, Result};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_HEAP_ID: AtomicU64 = AtomicU64::new(0);
+
+fn next_heap_id() -> u64 {
+    NEXT_HEAP_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// One change to the underlying vector, recorded so that it can be undone.
+#[derive(Debug, Clone)]
+enum Undo<T> {
+    Inserted,
+    Removed(T),
+    Swapped(usize, usize),
+    Replaced(usize, T),
+}
+
+impl<T: Copy> SiftHook<T> for Vec<Undo<T>> {
+    #[inline]
+    fn inserted(&mut self, _index: usize, _element: &T) {
+        self.push(Undo::Inserted);
+    }
+
+    #[inline]
+    fn removed(&mut self, _index: usize, element: &T) {
+        self.push(Undo::Removed(*element));
+    }
+
+    #[inline]
+    fn swapped(&mut self, _heap: &[T], a: usize, b: usize) {
+        self.push(Undo::Swapped(a, b));
+    }
+
+    #[inline]
+    fn replaced(&mut self, _heap: &[T], index: usize, old: &T) {
+        self.push(Undo::Replaced(index, *old));
+    }
+}
+
+/// A token naming a checkpoint of a [`TransactionalHeap`].
+/// It is returned by [`TransactionalHeap::checkpoint`] and consumed by `rollback` or `commit`
+/// on the same heap. Every other heap, including clones of the issuing heap, rejects it.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Checkpoint {
+    heap: u64,
+    level: usize,
+    id: u64,
+}
+
+/// A heap that can roll back every `insert`, `top`, `remove` and `update` made since a checkpoint.
+///
+/// While a checkpoint is open, the heap keeps an undo log of the element moves made by its sift loops,
+/// along with the elements that were pushed, popped or modified. Rolling back replays the log in reverse,
+/// so the cost of a checkpoint is proportional to the work done after it rather than to the size of the heap.
+/// Checkpoints nest: rolling back or committing a checkpoint also closes every checkpoint opened after it.
+/// A clone holds the same elements as the original but starts with no open checkpoints.
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{Min, TransactionalHeap};
+///
+/// let mut heap: TransactionalHeap<u32, Min, 2> = TransactionalHeap::new();
+/// heap.insert(5);
+/// heap.insert(3);
+///
+/// let outer = heap.checkpoint();
+/// heap.insert(1);
+/// let inner = heap.checkpoint();
+/// assert!(heap.top() == Some(1));
+/// heap.rollback(inner).unwrap();
+/// assert!(heap.peek() == Some(&1));
+/// heap.rollback(outer).unwrap();
+/// assert!(heap.as_heap().as_slice() == &[3, 5]);
+/// ```
+pub struct TransactionalHeap<T, O, const BRANCHES: usize>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    id: u64,
+    heap: Heap<T, O, BRANCHES>,
+    log: Vec<Undo<T>>,
+    checkpoints: Vec<(u64, usize)>,
+    next_id: u64,
+}
+
+impl<T, O, const BRANCHES: usize> Clone for TransactionalHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn clone(&self) -> Self {
+        Self::from(self.heap.clone())
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Debug for TransactionalHeap<T, O, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransactionalHeap")
+            .field("id", &self.id)
+            .field("heap", &self.heap)
+            .field("log", &self.log)
+            .field("checkpoints", &self.checkpoints)
+            .field("next_id", &self.next_id)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Default for TransactionalHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> From<Heap<T, O, BRANCHES>> for TransactionalHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    /// Wraps an existing heap without copying it.
+    fn from(heap: Heap<T, O, BRANCHES>) -> Self {
+        Self {
+            id: next_heap_id(),
+            heap,
+            log: Vec::new(),
+            checkpoints: Vec::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize> TransactionalHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    /// Constructs a new, empty heap.
+    pub fn new() -> Self {
+        Self::from(Heap::new())
+    }
+
+    /// Returns the underlying heap.
+    pub fn as_heap(&self) -> &Heap<T, O, BRANCHES> {
+        &self.heap
+    }
+
+    /// Consumes the wrapper and returns the underlying heap, keeping every change made so far.
+    pub fn into_heap(self) -> Heap<T, O, BRANCHES> {
+        self.heap
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns an immutable reference to the element on top of the heap without removing it or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Returns the number of checkpoints that are currently open.
+    pub fn depth(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Inserts an element into the heap and returns its index.
+    pub fn insert(&mut self, element: T) -> usize {
+        if self.checkpoints.is_empty() {
+            self.heap.insert_hooked(element, &mut ())
+        } else {
+            self.heap.insert_hooked(element, &mut self.log)
+        }
+    }
+
+    /// Removes and returns the element from the top of the heap. Returns `None` if the heap is empty.
+    pub fn top(&mut self) -> Option<T> {
+        if self.heap.is_empty() {
+            None
+        } else {
+            Some(self.remove_at(0))
+        }
+    }
+
+    /// Removes and returns the element at `index`.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<T> {
+        self.heap.check_index(index)?;
+        Ok(self.remove_at(index))
+    }
+
+    /// Updates the value of the element at `index` and returns its new index.
+    /// Returns an error if the heap is empty or if the index is out of bounds.
+    pub fn update<F>(&mut self, index: usize, modifier: F) -> Result<usize>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.heap.check_index(index)?;
+        let old: T = self.heap.heap[index];
+        modifier(&mut self.heap.heap[index]);
+        if self.checkpoints.is_empty() {
+            Ok(self.heap.restore_hooked(index, &mut ()))
+        } else {
+            self.log.replaced(&self.heap.heap, index, &old);
+            Ok(self.heap.restore_hooked(index, &mut self.log))
+        }
+    }
+
+    /// Clears the heap, removing all elements. While a checkpoint is open, the removals are logged so they can be rolled back.
+    pub fn clear(&mut self) {
+        if self.checkpoints.is_empty() {
+            self.heap.clear();
+        } else {
+            while let Some(element) = self.heap.heap.pop() {
+                self.log.removed(self.heap.heap.len(), &element);
+            }
+        }
+    }
+
+    /// Opens a new checkpoint and returns a token for it.
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        let id: u64 = self.next_id;
+        self.next_id += 1;
+        self.checkpoints.push((id, self.log.len()));
+        Checkpoint {
+            heap: self.id,
+            level: self.checkpoints.len() - 1,
+            id,
+        }
+    }
+
+    /// Undoes every change made since `token` was returned by [`TransactionalHeap::checkpoint`]
+    /// and closes that checkpoint along with every checkpoint opened after it.
+    /// Returns an error if the checkpoint has already been closed or was opened on another heap.
+    pub fn rollback(&mut self, token: Checkpoint) -> Result<()> {
+        let start: usize = self.close(&token)?;
+        while self.log.len() > start {
+            match self.log.pop().unwrap() {
+                Undo::Inserted => {
+                    self.heap.heap.pop();
+                }
+                Undo::Removed(element) => self.heap.heap.push(element),
+                Undo::Swapped(a, b) => self.heap.heap.swap(a, b),
+                Undo::Replaced(index, old) => self.heap.heap[index] = old,
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps every change made since `token` was returned by [`TransactionalHeap::checkpoint`]
+    /// and closes that checkpoint along with every checkpoint opened after it.
+    /// The changes can still be rolled back by an enclosing checkpoint.
+    /// Returns an error if the checkpoint has already been closed or was opened on another heap.
+    pub fn commit(&mut self, token: Checkpoint) -> Result<()> {
+        self.close(&token)?;
+        if self.checkpoints.is_empty() {
+            self.log.clear();
+        }
+        Ok(())
+    }
+
+    /// Closes the checkpoint named by `token` and every checkpoint opened after it,
+    /// and returns the length of the undo log when the checkpoint was opened.
+    fn close(&mut self, token: &Checkpoint) -> Result<usize> {
+        if token.heap != self.id {
+            return Err(Error::new(
+                ErrorKind::InvalidCheckpoint,
+                "The checkpoint belongs to another heap.",
+            ));
+        }
+        match self.checkpoints.get(token.level) {
+            Some(&(id, start)) if id == token.id => {
+                self.checkpoints.truncate(token.level);
+                Ok(start)
+            }
+            _ => Err(Error::new(
+                ErrorKind::InvalidCheckpoint,
+                "The checkpoint has already been rolled back or committed.",
+            )),
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> T {
+        if self.checkpoints.is_empty() {
+            self.heap.remove_hooked(index, &mut ())
+        } else {
+            self.heap.remove_hooked(index, &mut self.log)
+        }
+    }
+}
+
+impl<T, O, const BRANCHES: usize> PriorityQueue<T> for TransactionalHeap<T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    fn push(&mut self, element: T) {
+        self.insert(element);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        TransactionalHeap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.top()
+    }
+
+    fn len(&self) -> usize {
+        TransactionalHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        TransactionalHeap::clear(self)
+    }
+}
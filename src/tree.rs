@@ -0,0 +1,172 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::{Error, ErrorKind, Heap, Order, Result};
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+
+/// An iterator over the levels of a heap's tree, from the root down.
+/// Each level is yielded as a slice, and every level except the last holds `BRANCHES` times as many elements as the one above it.
+///
+/// This struct is created by [`Heap::levels`].
+#[derive(Debug, Clone)]
+pub struct Levels<'a, T, const BRANCHES: usize> {
+    pub(crate) heap: &'a [T],
+    pub(crate) width: usize,
+}
+
+impl<'a, T, const BRANCHES: usize> Iterator for Levels<'a, T, BRANCHES> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let (level, rest) = self.heap.split_at(self.width.min(self.heap.len()));
+        self.heap = rest;
+        self.width = self.width.saturating_mul(BRANCHES);
+        Some(level)
+    }
+}
+
+impl<T, const BRANCHES: usize> FusedIterator for Levels<'_, T, BRANCHES> {}
+
+/// An iterator over the elements of a heap in pre-order, that is, each element followed by the subtrees of its children.
+/// It yields the index of each element along with a reference to it.
+///
+/// This struct is created by [`Heap::iter_pre_order`].
+#[derive(Debug, Clone)]
+pub struct PreOrder<'a, T, const BRANCHES: usize> {
+    pub(crate) heap: &'a [T],
+    pub(crate) stack: Vec<usize>,
+}
+
+impl<'a, T, const BRANCHES: usize> Iterator for PreOrder<'a, T, BRANCHES> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<(usize, &'a T)> {
+        let index: usize = self.stack.pop()?;
+        let first_child: usize = (index * BRANCHES) + 1;
+        let last_child: usize = (first_child + BRANCHES).min(self.heap.len());
+        self.stack.extend((first_child..last_child).rev());
+        Some((index, &self.heap[index]))
+    }
+}
+
+impl<T, const BRANCHES: usize> FusedIterator for PreOrder<'_, T, BRANCHES> {}
+
+/// A cursor that walks the tree of a [`Heap`], reads the element at its current node,
+/// and applies checked updates to it.
+///
+/// The cursor starts at the root. After an update it follows the updated element to its new position.
+///
+/// This struct is created by [`Heap::cursor`].
+///
+/// ## Example:
+///
+/// ```
+/// use rheap::{Heap, Min};
+///
+/// let v: Vec<usize> = vec![1, 2, 3, 4, 5, 6, 7];
+/// let mut heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+/// let mut cursor = heap.cursor();
+/// assert!(cursor.to_child(1) && cursor.to_child(0));
+/// assert!(cursor.index() == 5 && cursor.get() == Some(&6));
+/// cursor.update(|x| *x = 0).unwrap();
+/// assert!(cursor.index() == 0 && cursor.is_root());
+/// ```
+pub struct Cursor<'a, T, O, const BRANCHES: usize>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    pub(crate) heap: &'a mut Heap<T, O, BRANCHES>,
+    pub(crate) index: usize,
+}
+
+impl<'a, T, O, const BRANCHES: usize> Debug for Cursor<'a, T, O, BRANCHES>
+where
+    T: Copy + Debug,
+    O: Order<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cursor")
+            .field("heap", &self.heap)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<T, O, const BRANCHES: usize> Cursor<'_, T, O, BRANCHES>
+where
+    T: Copy,
+    O: Order<T>,
+{
+    /// Returns the index of the current node.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the element at the current node, or `None` if the heap is empty.
+    pub fn get(&self) -> Option<&T> {
+        self.heap.heap.get(self.index)
+    }
+
+    /// Returns true if the cursor is at the root of the tree.
+    pub fn is_root(&self) -> bool {
+        self.index == 0
+    }
+
+    /// Returns the number of edges between the root and the current node.
+    pub fn depth(&self) -> usize {
+        self.heap.depth(self.index).unwrap_or(0)
+    }
+
+    /// Moves the cursor to the root of the tree.
+    pub fn to_root(&mut self) {
+        self.index = 0;
+    }
+
+    /// Moves the cursor to the parent of the current node.
+    /// Returns false, leaving the cursor where it is, if the current node is the root.
+    pub fn to_parent(&mut self) -> bool {
+        match self.heap.parent(self.index) {
+            Some(parent) => {
+                self.index = parent;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the cursor to the `n`th child of the current node, counting from zero.
+    /// Returns false, leaving the cursor where it is, if the current node has no such child.
+    pub fn to_child(&mut self, n: usize) -> bool {
+        let children: std::ops::Range<usize> = self.heap.children(self.index);
+        if n < children.len() {
+            self.index = children.start + n;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Updates the element at the current node and restores the heap property.
+    /// The cursor moves with the element and the value returned by `modifier` is passed back.
+    /// Returns an error if the heap is empty.
+    pub fn update<F, R>(&mut self, modifier: F) -> Result<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        if self.heap.is_empty() {
+            return Err(Error::new(
+                ErrorKind::EmptyHeap,
+                "Can not update an element of an empty heap.",
+            ));
+        }
+        let (index, result) = self.heap.update_with(self.index, modifier)?;
+        self.index = index;
+        Ok(result)
+    }
+}
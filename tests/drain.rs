@@ -0,0 +1,64 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{Heap, Max, Min};
+
+    const COUNT: usize = 5000;
+
+    fn random_heap<O: rheap::Order<usize>>(seed: u64) -> (Heap<usize, O, 3>, Vec<usize>) {
+        let mut rng: StdRng = StdRng::seed_from_u64(seed);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..1000)).collect();
+        (Heap::from(&v[..]), v)
+    }
+
+    #[test]
+    pub fn test_drain() {
+        let (mut heap, mut v) = random_heap::<Min>(1);
+        let mut drained: Vec<usize> = heap.drain().collect();
+        drained.sort_unstable();
+        v.sort_unstable();
+        assert!(drained == v);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    pub fn test_drain_sorted() {
+        let (mut heap, mut v) = random_heap::<Max>(2);
+        v.sort_unstable_by(|a, b| b.cmp(a));
+        let first: Vec<usize> = heap.drain_sorted().take(100).collect();
+        assert!(first[..] == v[..100]);
+        assert!(
+            heap.is_empty(),
+            "dropping DrainSorted did not remove the rest"
+        );
+    }
+
+    #[test]
+    pub fn test_drain_while() {
+        let (mut heap, v) = random_heap::<Min>(3);
+        let expired: Vec<usize> = heap.drain_while(|&x| x < 250).collect();
+        assert!(expired.windows(2).all(|w| w[0] <= w[1]));
+        assert!(expired.len() == v.iter().filter(|&&x| x < 250).count());
+        assert!(heap.len() + expired.len() == COUNT);
+        assert!(heap.iter().all(|&x| x >= 250));
+        assert!(heap.is_valid());
+    }
+
+    #[test]
+    pub fn test_retain() {
+        let (mut heap, v) = random_heap::<Max>(4);
+        heap.retain(|x| x % 3 == 0);
+        assert!(heap.len() == v.iter().filter(|&&x| x % 3 == 0).count());
+        assert!(heap.is_valid());
+
+        heap.retain_mut(|x| {
+            *x = 2000 - *x;
+            *x % 2 == 0
+        });
+        assert!(heap.iter().all(|&x| x > 1000 && x % 2 == 0));
+        assert!(heap.is_valid());
+        let sorted: Vec<usize> = heap.drain_sorted().collect();
+        assert!(sorted.windows(2).all(|w| w[0] >= w[1]));
+    }
+}
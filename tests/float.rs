@@ -0,0 +1,82 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{
+        ErrorKind, FloatHeap, FloatMax, FloatMin, Heap, NanFirst, NanLast, NanReject, Order,
+    };
+
+    const COUNT: usize = 2000;
+
+    fn random_floats(rng: &mut StdRng, nan_every: usize) -> Vec<f64> {
+        (0..COUNT)
+            .map(|i| {
+                if nan_every > 0 && i % nan_every == 0 {
+                    f64::NAN
+                } else {
+                    rng.gen_range(-1000.0..1000.0)
+                }
+            })
+            .collect()
+    }
+
+    fn drain<O: Order<f64>>(mut heap: Heap<f64, O, 3>) -> Vec<f64> {
+        let mut v: Vec<f64> = Vec::new();
+        while let Some(x) = heap.top() {
+            assert!(heap.is_valid());
+            v.push(x);
+        }
+        v
+    }
+
+    #[test]
+    pub fn test_nan_first() {
+        let mut rng: StdRng = StdRng::seed_from_u64(1);
+        let v: Vec<f64> = random_floats(&mut rng, 10);
+        let nans: usize = v.iter().filter(|x| x.is_nan()).count();
+        let sorted: Vec<f64> = drain(Heap::<f64, FloatMin<NanFirst>, 3>::from(&v[..]));
+        assert!(sorted[..nans].iter().all(|x| x.is_nan()));
+        assert!(sorted[nans..].windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    pub fn test_nan_last() {
+        let mut rng: StdRng = StdRng::seed_from_u64(2);
+        let v: Vec<f64> = random_floats(&mut rng, 7);
+        let nans: usize = v.iter().filter(|x| x.is_nan()).count();
+        let sorted: Vec<f64> = drain(Heap::<f64, FloatMax<NanLast>, 3>::from(&v[..]));
+        let split: usize = sorted.len() - nans;
+        assert!(sorted[..split].windows(2).all(|w| w[0] >= w[1]));
+        assert!(sorted[split..].iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    pub fn test_nan_reject() {
+        let mut rng: StdRng = StdRng::seed_from_u64(3);
+        let v: Vec<f64> = random_floats(&mut rng, 0);
+        let mut heap: FloatHeap<f64, FloatMin<NanReject>, 2> = FloatHeap::try_from(&v[..]).unwrap();
+
+        for _ in 0..COUNT {
+            let index: usize = rng.gen_range(0..heap.len());
+            let before: Vec<f64> = heap.as_slice().to_vec();
+            let err = heap.update(index, |x| *x = f64::NAN).unwrap_err();
+            assert!(err.kind() == ErrorKind::NotANumber);
+            assert!(
+                heap.as_slice() == &before[..],
+                "a rejected update modified the heap"
+            );
+            assert!(heap.insert(f64::NAN).unwrap_err().kind() == ErrorKind::NotANumber);
+
+            let x: f64 = rng.gen_range(-1000.0..1000.0);
+            heap.update(index, |y| *y = x).unwrap();
+            heap.insert(x).unwrap();
+            heap.top().unwrap();
+            assert!(heap.as_heap().is_valid());
+        }
+        assert!(heap.iter().all(|x| !x.is_nan()));
+
+        let with_nan: Vec<f64> = vec![1.0, f64::NAN];
+        let result = FloatHeap::<f64, FloatMin<NanReject>, 2>::try_from(&with_nan[..]);
+        assert!(result.unwrap_err().kind() == ErrorKind::NotANumber);
+    }
+}
@@ -2,28 +2,29 @@
 pub mod test {
 
     use rand::prelude::*;
-    use rheap::Heap;
+    use rheap::{ErrorKind, FloatMin, Heap, Max, Min, NanLast, Order};
 
     const COUNT: usize = 10000;
 
     #[test]
     pub fn test_min_heap() {
-        test_heap::<3, false>();
+        test_heap::<3, Min>();
     }
 
     #[test]
     pub fn test_max_heap() {
-        test_heap::<3, true>();
+        test_heap::<3, Max>();
     }
 
-    pub fn test_heap<const D: usize, const H: bool>() {
+    #[allow(clippy::collapsible_if)]
+    pub fn test_heap<const D: usize, O: Order<usize>>() {
         let mut v: Vec<usize> = vec![0; COUNT];
         for _ in 0..COUNT {
             v.push(rand::thread_rng().gen_range(0..10000));
         }
         //rand::thread_rng().fill(&mut v[..]);
 
-        let mut heap: Heap<usize, H, D> = Heap::from(&v[..]);
+        let mut heap: Heap<usize, O, D> = Heap::from(&v[..]);
 
         assert!(v.len() == heap.len(), "Not all elements were loaded by from().");
 
@@ -115,4 +116,414 @@ pub mod test {
             prev_choice = choice;
         }
     }
+
+    #[test]
+    pub fn test_push_pop_and_replace_top() {
+        let mut rng: StdRng = StdRng::seed_from_u64(33);
+        let mut heap: Heap<usize, Min, 4> = Heap::new();
+        let mut model: Vec<usize> = Vec::new();
+
+        assert!(heap.push_pop(7) == 7, "push_pop() on an empty heap failed");
+        assert!(heap.is_empty());
+        assert!(heap.try_replace_top(7).is_err());
+        assert!(heap.is_empty());
+        assert!(heap.replace_top(7).is_none());
+        model.push(7);
+
+        for _ in 0..COUNT {
+            let n: usize = rng.gen_range(0..1000);
+            if rng.gen::<bool>() {
+                model.push(n);
+                model.sort_unstable();
+                let expected: usize = model.remove(0);
+                assert!(heap.push_pop(n) == expected);
+            } else {
+                model.sort_unstable();
+                let expected: usize = model.remove(0);
+                model.push(n);
+                assert!(heap.try_replace_top(n).unwrap() == expected);
+            }
+            if rng.gen_range(0..4) == 0 {
+                heap.insert(n);
+                model.push(n);
+            }
+            assert!(heap.is_valid());
+            assert!(heap.len() == model.len());
+        }
+    }
+
+    #[test]
+    pub fn test_pop_ties() {
+        let mut rng: StdRng = StdRng::seed_from_u64(34);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..50)).collect();
+        let mut heap: Heap<usize, Max, 3> = Heap::from(&v[..]);
+        let mut other: Heap<usize, Max, 3> = heap.clone();
+
+        let mut partial: Heap<usize, Max, 3> = heap.clone();
+        let top: usize = *partial.peek().unwrap();
+        let count: usize = v.iter().filter(|&&x| x == top).count();
+        assert!(partial.pop_equal_iter().len() == count);
+        assert!(partial.pop_equal_iter().take(count / 2).all(|x| x == top));
+        assert!(partial.is_valid() && partial.len() == COUNT - count / 2);
+        assert!(partial.pop_ties().len() == count - count / 2);
+
+        for expected in (0..50).rev() {
+            let count: usize = v.iter().filter(|&&x| x == expected).count();
+            let ties: Vec<usize> = heap.pop_ties();
+            assert!(ties.len() == count && ties.iter().all(|&x| x == expected));
+            assert!(heap.is_valid());
+            let ties: Vec<usize> = other.pop_equal_iter().collect();
+            assert!(ties.len() == count && ties.iter().all(|&x| x == expected));
+        }
+        assert!(heap.is_empty() && other.is_empty());
+        assert!(heap.pop_ties().is_empty());
+        assert!(other.pop_equal_iter().next().is_none());
+    }
+
+    #[test]
+    pub fn test_threshold_queries() {
+        let mut rng: StdRng = StdRng::seed_from_u64(35);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..COUNT)).collect();
+        let heap: Heap<usize, Min, 4> = Heap::from(&v[..]);
+
+        for _ in 0..100 {
+            let bound: usize = rng.gen_range(0..COUNT);
+            let mut expected: Vec<usize> = v.iter().copied().filter(|&x| x < bound).collect();
+            let mut found: Vec<usize> = heap.iter_better_than(&bound).copied().collect();
+            expected.sort_unstable();
+            found.sort_unstable();
+            assert!(found == expected, "iter_better_than() visited the wrong elements");
+            assert!(heap.count_better_than(&bound) == expected.len());
+
+            let x: usize = rng.gen_range(0..COUNT);
+            assert!(heap.contains(&x) == v.contains(&x));
+            if let Some(index) = heap.find(&x) {
+                assert!(heap.as_slice()[index] == x);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_value_based_removal() {
+        let mut rng: StdRng = StdRng::seed_from_u64(36);
+        let mut v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..100)).collect();
+        let mut heap: Heap<usize, Max, 2> = Heap::from(&v[..]);
+
+        for _ in 0..50 {
+            let x: usize = rng.gen_range(0..120);
+            match v.iter().position(|&y| y == x) {
+                Some(i) => {
+                    v.swap_remove(i);
+                    assert!(heap.remove_item(&x).unwrap() == x);
+                }
+                None => assert!(heap.remove_item(&x).unwrap_err().kind() == ErrorKind::NotFound),
+            }
+            assert!(heap.is_valid());
+
+            let y: usize = rng.gen_range(0..120);
+            match v.iter().position(|&z| z == y) {
+                Some(i) => {
+                    v[i] = rng.gen_range(0..100);
+                    let z: usize = v[i];
+                    heap.update_item(&y, |w| *w = z).unwrap();
+                }
+                None => assert!(heap.update_item(&y, |w| *w = 0).is_err()),
+            }
+            assert!(heap.is_valid());
+        }
+
+        let count: usize = v.iter().filter(|&&x| x == 42).count();
+        assert!(heap.remove_all(&42) == count);
+        assert!(!heap.contains(&42));
+        v.retain(|&x| x != 42);
+
+        let removed: Vec<usize> = heap.remove_where(|x| x % 5 == 0);
+        assert!(removed.len() == v.iter().filter(|&&x| x % 5 == 0).count());
+        assert!(removed.iter().all(|x| x % 5 == 0));
+        assert!(heap.iter().all(|x| x % 5 != 0));
+        assert!(heap.len() + removed.len() == v.len());
+        assert!(heap.is_valid());
+    }
+
+    #[test]
+    pub fn test_update_variants() {
+        let mut rng: StdRng = StdRng::seed_from_u64(37);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..COUNT)).collect();
+        let mut heap: Heap<usize, Min, 3> = Heap::from(&v[..]);
+        let mut calls: usize = 0;
+
+        for _ in 0..COUNT {
+            let index: usize = rng.gen_range(0..heap.len());
+            let n: usize = rng.gen_range(0..COUNT);
+            let (new_index, old) = heap
+                .update_with(index, |x| {
+                    calls += 1;
+                    std::mem::replace(x, n)
+                })
+                .unwrap();
+            assert!(heap.as_slice()[new_index] == n);
+            assert!(old <= COUNT);
+
+            let index: usize = rng.gen_range(0..heap.len());
+            let before: Vec<usize> = heap.as_slice().to_vec();
+            let limit: usize = rng.gen_range(0..COUNT);
+            let result = heap
+                .try_update(index, |x| {
+                    *x = n;
+                    if n < limit {
+                        Ok(n)
+                    } else {
+                        Err(limit)
+                    }
+                })
+                .unwrap();
+            match result {
+                Ok((new_index, m)) => assert!(m == n && heap.as_slice()[new_index] == n),
+                Err(e) => assert!(e == limit && heap.as_slice() == &before[..]),
+            }
+            assert!(heap.is_valid());
+        }
+        assert!(calls == COUNT);
+        assert!(heap.update_with(COUNT * 2, |_| ()).is_err());
+        assert!(heap.try_update(COUNT * 2, |_| Ok::<(), ()>(())).is_err());
+    }
+
+    fn is_min_heap<const D: usize>(s: &[usize]) -> bool {
+        (1..s.len()).all(|i| s[(i - 1) / D] <= s[i])
+    }
+
+    #[test]
+    pub fn test_batch_updates() {
+        let mut rng: StdRng = StdRng::seed_from_u64(38);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..COUNT)).collect();
+        let mut heap: Heap<usize, Min, 3> = Heap::from(&v[..]);
+
+        for k in [1, 5, 50, COUNT / 2, COUNT * 2] {
+            let indices: Vec<usize> = (0..k).map(|_| rng.gen_range(0..heap.len())).collect();
+            let mut expected: Vec<usize> = heap.as_slice().to_vec();
+            for &i in indices.iter() {
+                expected[i] = (expected[i] * 7 + 13) % COUNT;
+            }
+            heap.update_many(indices.iter().copied(), |x| *x = (*x * 7 + 13) % COUNT)
+                .unwrap();
+            assert!(is_min_heap::<3>(heap.as_slice()));
+            let mut actual: Vec<usize> = heap.as_slice().to_vec();
+            actual.sort_unstable();
+            expected.sort_unstable();
+            assert!(actual == expected);
+        }
+
+        let before: Vec<usize> = heap.as_slice().to_vec();
+        let err = heap.update_many([0, COUNT * 2], |x| *x += 1).unwrap_err();
+        assert!(err.kind() == ErrorKind::InvalidIndex);
+        assert!(heap.as_slice() == &before[..]);
+
+        heap.update_all(|x| *x = COUNT - *x);
+        assert!(is_min_heap::<3>(heap.as_slice()));
+        assert!(heap.len() == COUNT);
+
+        {
+            let mut slice = heap.as_mut_slice();
+            for x in slice.iter_mut() {
+                *x = rng.gen_range(0..COUNT);
+            }
+            slice[COUNT - 1] = 0;
+        }
+        assert!(is_min_heap::<3>(heap.as_slice()));
+        assert!(heap.peek() == Some(&0));
+    }
+
+    #[test]
+    pub fn test_split_and_partition() {
+        let mut rng: StdRng = StdRng::seed_from_u64(39);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..COUNT)).collect();
+        let mut heap: Heap<usize, Min, 4> = Heap::from(&v[..]);
+
+        let small: Heap<usize, Min, 4> = heap.split_off_where(|x| *x < COUNT / 3);
+        assert!(small.len() + heap.len() == COUNT);
+        assert!(is_min_heap::<4>(small.as_slice()) && is_min_heap::<4>(heap.as_slice()));
+        assert!(small.iter().all(|x| *x < COUNT / 3));
+        assert!(heap.iter().all(|x| *x >= COUNT / 3));
+
+        let len: usize = heap.len();
+        let (even, odd) = heap.partition(|x| x % 2 == 0);
+        assert!(even.len() + odd.len() == len);
+        assert!(is_min_heap::<4>(even.as_slice()) && is_min_heap::<4>(odd.as_slice()));
+        assert!(even.iter().all(|x| x % 2 == 0) && odd.iter().all(|x| x % 2 == 1));
+
+        let mut heap: Heap<usize, Min, 4> = Heap::from(&v[..]);
+        let mut sorted: Vec<usize> = v.clone();
+        sorted.sort_unstable();
+        let best: Heap<usize, Min, 4> = heap.split_top(100);
+        assert!(best.as_slice() == &sorted[..100]);
+        assert!(is_min_heap::<4>(heap.as_slice()));
+        assert!(heap.len() == COUNT - 100);
+        assert!(heap.peek() == Some(&sorted[100]));
+        let rest: Heap<usize, Min, 4> = heap.split_top(COUNT);
+        assert!(heap.is_empty() && rest.len() == COUNT - 100);
+    }
+
+    #[test]
+    pub fn test_conversions() {
+        let mut rng: StdRng = StdRng::seed_from_u64(40);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..COUNT)).collect();
+        let mut sorted: Vec<usize> = v.clone();
+        sorted.sort_unstable();
+
+        let heap: Heap<usize, Min, 2> = Heap::from(&v[..]);
+        let capacity: usize = heap.capacity();
+        let mut heap: Heap<usize, Max, 2> = heap.into_reversed();
+        assert!(heap.capacity() == capacity);
+        assert!((1..COUNT).all(|i| heap.as_slice()[(i - 1) / 2] >= heap.as_slice()[i]));
+        assert!(heap.top() == sorted.last().copied());
+
+        let heap: Heap<usize, Max, 5> = heap.into_arity::<5>();
+        assert!((1..heap.len()).all(|i| heap.as_slice()[(i - 1) / 5] >= heap.as_slice()[i]));
+
+        let mut heap: Heap<usize, Min, 3> = heap.convert::<Min, 3>();
+        assert!(is_min_heap::<3>(heap.as_slice()));
+        assert!(heap.peek() == Some(&sorted[0]));
+
+        let mut other: Heap<usize, Min, 7> = Heap::from(&v[..]);
+        heap.append(&mut other);
+        assert!(other.is_empty());
+        assert!(heap.len() == COUNT * 2 - 1);
+        assert!(is_min_heap::<3>(heap.as_slice()));
+    }
+
+    #[test]
+    pub fn test_iter_sorted() {
+        let mut rng: StdRng = StdRng::seed_from_u64(41);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..COUNT)).collect();
+        let mut sorted: Vec<usize> = v.clone();
+        sorted.sort_unstable();
+
+        let heap: Heap<usize, Min, 3> = Heap::from(&v[..]);
+        let before: Vec<usize> = heap.as_slice().to_vec();
+        let iter = heap.iter_sorted();
+        assert!(iter.len() == COUNT);
+        let all: Vec<usize> = iter.copied().collect();
+        assert!(all == sorted);
+        assert!(heap.as_slice() == &before[..]);
+
+        let top: Vec<&usize> = heap.top_k_ref(20);
+        assert!(top.len() == 20);
+        assert!(top.iter().zip(sorted.iter()).all(|(a, b)| **a == *b));
+        assert!(heap.top_k_ref(COUNT * 2).len() == COUNT);
+
+        let heap: Heap<usize, Max, 5> = Heap::from(&v[..]);
+        sorted.reverse();
+        assert!(heap.iter_sorted().copied().eq(sorted.iter().copied()));
+        assert!(Heap::<usize, Max, 5>::new().iter_sorted().next().is_none());
+    }
+
+    #[test]
+    pub fn test_tree_navigation() {
+        let mut rng: StdRng = StdRng::seed_from_u64(42);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(1..COUNT)).collect();
+        let mut heap: Heap<usize, Min, 3> = Heap::from(&v[..]);
+
+        assert!(heap.parent(0).is_none() && heap.parent(COUNT).is_none());
+        for i in 0..COUNT {
+            for c in heap.children(i) {
+                assert!(heap.parent(c) == Some(i));
+                assert!(heap.depth(c) == heap.depth(i).map(|d| d + 1));
+            }
+        }
+        assert!(heap.children(COUNT).is_empty() && heap.depth(COUNT).is_none());
+
+        let levels: Vec<&[usize]> = heap.levels().collect();
+        assert!(levels.len() == heap.height());
+        assert!(levels.iter().map(|l| l.len()).sum::<usize>() == COUNT);
+        for (d, level) in levels.iter().enumerate().take(levels.len() - 1) {
+            assert!(level.len() == 3usize.pow(d as u32));
+        }
+
+        let pre: Vec<usize> = heap.iter_pre_order().map(|(i, _)| i).collect();
+        assert!(pre.len() == COUNT && pre[0] == 0);
+        for w in pre.windows(2) {
+            // the parent of the next element is the previous element or one of its ancestors
+            let p: usize = heap.parent(w[1]).unwrap();
+            let mut a: usize = w[0];
+            while a > p {
+                a = heap.parent(a).unwrap();
+            }
+            assert!(a == p);
+        }
+
+        let height: usize = levels.len();
+        let mut cursor = heap.cursor();
+        while cursor.to_child(2) {}
+        assert!(cursor.depth() < height);
+        let deep: usize = cursor.index();
+        let value: usize = *cursor.get().unwrap();
+        assert!(cursor.update(|x| std::mem::replace(x, 0)).unwrap() == value);
+        assert!(cursor.is_root() && cursor.get() == Some(&0));
+        assert!(!cursor.to_parent());
+        assert!(deep > 0 && heap.is_valid());
+
+        let mut empty: Heap<usize, Min, 3> = Heap::new();
+        assert!(empty.height() == 0 && empty.levels().next().is_none());
+        let mut cursor = empty.cursor();
+        assert!(cursor.get().is_none() && !cursor.to_child(0));
+        assert!(cursor.update(|x| *x += 1).unwrap_err().kind() == ErrorKind::EmptyHeap);
+    }
+
+    #[test]
+    pub fn test_rendering() {
+        let mut rng: StdRng = StdRng::seed_from_u64(55);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..COUNT)).collect();
+        let mut heap: Heap<usize, Min, 3> = Heap::from(&v[..]);
+        assert!(heap.violations().is_empty());
+
+        let text: String = heap.to_string();
+        assert!(text.lines().count() == COUNT);
+        assert!(text.lines().next() == Some(heap.peek().unwrap().to_string().as_str()));
+        for depth in 1..heap.height() {
+            let shown: usize = heap.levels().take(depth).map(|l| l.len()).sum();
+            let text: String = heap.display_depth(depth).to_string();
+            let elided: usize = text.lines().filter(|l| l.ends_with("...")).count();
+            assert!(text.lines().count() == shown + elided);
+        }
+        assert!(heap.display_depth(0).to_string().is_empty());
+        let floats: Heap<f64, FloatMin<NanLast>, 2> = Heap::from(&[1.25, 2.5, 3.75][..]);
+        assert!(format!("{:.1}", floats) == "1.2\n|-- 2.5\n`-- 3.8\n");
+        assert!(Heap::<usize, Min, 3>::new().to_string().is_empty());
+
+        // corrupt the heap by skipping the rebuild that the guard performs on drop
+        let mut slice = heap.as_mut_slice();
+        slice[COUNT - 1] = 0;
+        slice[0] = COUNT;
+        std::mem::forget(slice);
+        let violations: Vec<usize> = heap.violations();
+        assert!(violations.contains(&(COUNT - 1)));
+        assert!(violations.iter().all(|&i| i == COUNT - 1 || i <= 3));
+
+        let dot: String = heap.to_dot(&violations);
+        assert!(dot.lines().filter(|l| l.contains("->")).count() == COUNT - 1);
+        assert!(dot.matches("fillcolor=red").count() == violations.len());
+        assert!(dot.ends_with("}\n"));
+    }
+
+    // A policy that is neither Clone nor Debug.
+    struct ByLength;
+
+    impl Order<&'static str> for ByLength {
+        fn compare(a: &&'static str, b: &&'static str) -> std::cmp::Ordering {
+            a.len().cmp(&b.len())
+        }
+    }
+
+    #[test]
+    pub fn test_clone_and_debug_without_policy_bounds() {
+        let mut heap: Heap<&'static str, ByLength, 2> = Heap::new();
+        for word in ["three", "one", "eleven", "four"] {
+            heap.insert(word);
+        }
+        let mut copy: Heap<&'static str, ByLength, 2> = heap.clone();
+        assert!(copy.top() == Some("one"));
+        assert!(heap.len() == 4 && copy.len() == 3);
+        assert!(format!("{:?}", heap).starts_with("Heap { heap: [\"one\""));
+        assert!(format!("{:?}", heap.iter_sorted()).starts_with("IterSorted"));
+    }
 }
@@ -0,0 +1,114 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{ErrorKind, FloatMin, IndexHeap, Max, Min, NanLast, Order};
+    use std::cmp::Ordering;
+
+    const COUNT: usize = 5000;
+    const KEYS: usize = 300;
+
+    #[test]
+    pub fn test_min_index_heap() {
+        test_index_heap::<Min, 2>();
+    }
+
+    #[test]
+    pub fn test_max_index_heap() {
+        test_index_heap::<Max, 4>();
+    }
+
+    #[test]
+    pub fn test_key_limit() {
+        let mut heap: IndexHeap<u32, Min, 2> = IndexHeap::new();
+        assert!(heap.key_limit() == usize::MAX);
+        assert!(heap.insert(usize::MAX, 1).unwrap_err().kind() == ErrorKind::InvalidIndex);
+        assert!(!heap.contains(usize::MAX) && heap.delete(usize::MAX).is_err());
+        heap.insert(1 << 21, 1).unwrap();
+        heap.insert(KEYS, 2).unwrap();
+        assert!(heap.len() == 2 && heap.is_valid());
+
+        let mut heap: IndexHeap<u32, Max, 3> = IndexHeap::with_key_limit(KEYS);
+        assert!(heap.key_limit() == KEYS);
+        assert!(heap.insert(KEYS, 1).unwrap_err().kind() == ErrorKind::InvalidIndex);
+        heap.insert(KEYS - 1, 1).unwrap();
+        assert!(heap.len() == 1 && heap.is_valid());
+    }
+
+    #[test]
+    pub fn test_float_decrease_key() {
+        let mut heap: IndexHeap<f64, FloatMin<NanLast>, 2> = IndexHeap::with_capacity(3);
+        heap.insert(0, 4.5).unwrap();
+        heap.insert(1, 2.5).unwrap();
+        heap.insert(2, 9.0).unwrap();
+        heap.decrease(2, 1.5).unwrap();
+        assert!(heap.decrease(2, 3.0).unwrap_err().kind() == ErrorKind::InvalidPriority);
+        heap.increase(1, 7.0).unwrap();
+        assert!(heap.increase(1, 0.5).unwrap_err().kind() == ErrorKind::InvalidPriority);
+        assert!(heap.pop() == Some((2, 1.5)) && heap.pop() == Some((0, 4.5)));
+        assert!(heap.pop() == Some((1, 7.0)));
+    }
+
+    fn best<O: Order<u32>>(model: &[Option<u32>]) -> Option<u32> {
+        model.iter().flatten().copied().min_by(O::compare)
+    }
+
+    pub fn test_index_heap<O: Order<u32>, const D: usize>() {
+        let mut rng: StdRng = StdRng::seed_from_u64(D as u64);
+        let mut heap: IndexHeap<u32, O, D> = IndexHeap::new();
+        let mut model: Vec<Option<u32>> = vec![None; KEYS];
+
+        for _ in 0..COUNT {
+            let key: usize = rng.gen_range(0..KEYS);
+            let p: u32 = rng.gen_range(0..1000);
+            match rng.gen_range(0..6) {
+                0 | 1 => {
+                    let result = heap.insert(key, p);
+                    assert!(result.is_ok() == model[key].is_none());
+                    model[key].get_or_insert(p);
+                }
+                2 => {
+                    let result = heap.change(key, p);
+                    assert!(result.ok() == model[key]);
+                    if model[key].is_some() {
+                        model[key] = Some(p);
+                    }
+                }
+                3 => {
+                    let ok: bool =
+                        model[key].is_some_and(|old| O::compare(&p, &old) != Ordering::Greater);
+                    assert!(heap.decrease(key, p).is_ok() == ok);
+                    if ok {
+                        model[key] = Some(p);
+                    }
+                }
+                4 => {
+                    let ok: bool =
+                        model[key].is_some_and(|old| O::compare(&p, &old) != Ordering::Less);
+                    assert!(heap.increase(key, p).is_ok() == ok);
+                    if ok {
+                        model[key] = Some(p);
+                    }
+                }
+                _ => {
+                    if rng.gen::<bool>() {
+                        assert!(heap.delete(key).ok() == model[key].take());
+                    } else if let Some((key, p)) = heap.pop() {
+                        assert!(Some(p) == best::<O>(&model));
+                        assert!(model[key].take() == Some(p));
+                    } else {
+                        assert!(best::<O>(&model).is_none());
+                    }
+                }
+            }
+            assert!(heap.is_valid());
+            assert!(heap.contains(key) == model[key].is_some());
+            assert!(heap.priority(key) == model[key].as_ref());
+            assert!(heap.peek().map(|(_, &p)| p) == best::<O>(&model));
+        }
+
+        heap.clear();
+        assert!(heap.is_empty());
+        assert!(heap.is_valid());
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{
+        read_journal, replay, ErrorKind, FileSink, Heap, JournalEntry, JournalError, JournalSink,
+        JournaledHeap, Min,
+    };
+    use std::io::BufReader;
+
+    const COUNT: usize = 2000;
+
+    fn random_ops<S: JournalSink<usize>>(
+        heap: &mut JournaledHeap<usize, Min, 4, S>,
+        rng: &mut StdRng,
+    ) {
+        for _ in 0..COUNT {
+            match rng.gen_range(0..8) {
+                0..=3 => {
+                    heap.insert(rng.gen_range(0..COUNT));
+                }
+                4 => {
+                    heap.top();
+                }
+                5 => {
+                    let _ = heap.remove(rng.gen_range(0..heap.len() + 2));
+                }
+                6 => {
+                    let value: usize = rng.gen_range(0..COUNT);
+                    let _ = heap.update(rng.gen_range(0..heap.len() + 2), |x| *x = value);
+                }
+                _ if rng.gen_range(0..50) == 0 => heap.clear(),
+                _ => (),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_in_memory_replay() {
+        let mut rng: StdRng = StdRng::seed_from_u64(51);
+        let mut heap: JournaledHeap<usize, Min, 4, Vec<JournalEntry<usize>>> =
+            JournaledHeap::new(Vec::new());
+        random_ops(&mut heap, &mut rng);
+        let (heap, mut journal) = heap.into_parts();
+        assert!(journal.len() <= COUNT);
+
+        let rebuilt: Heap<usize, Min, 4> = replay(&journal).unwrap();
+        assert!(rebuilt.as_slice() == heap.as_slice());
+
+        // tamper with the result of one call and the replay stops right there
+        let step: usize = journal
+            .iter()
+            .position(|e| matches!(e, JournalEntry::Top { result: Some(_) }))
+            .unwrap();
+        journal[step] = JournalEntry::Top {
+            result: Some(COUNT),
+        };
+        let err: JournalError = replay::<usize, Min, 4>(&journal).unwrap_err();
+        assert!(err.step() == step && err.line().is_none());
+        assert!(err.error().kind() == ErrorKind::ReplayMismatch);
+    }
+
+    #[test]
+    pub fn test_text_journal() {
+        let mut rng: StdRng = StdRng::seed_from_u64(52);
+        let mut heap: JournaledHeap<usize, Min, 4, FileSink<Vec<u8>>> =
+            JournaledHeap::new(FileSink::new(Vec::new()));
+        random_ops(&mut heap, &mut rng);
+        heap.sink_mut().flush().unwrap();
+        let (heap, sink) = heap.into_parts();
+        let text: Vec<u8> = sink.into_inner();
+
+        let journal: Vec<JournalEntry<usize>> = read_journal(BufReader::new(&text[..])).unwrap();
+        let rebuilt: Heap<usize, Min, 4> = replay(&journal).unwrap();
+        assert!(rebuilt.as_slice() == heap.as_slice());
+        for entry in journal.iter() {
+            assert!(entry.to_string().parse::<JournalEntry<usize>>().unwrap() == *entry);
+        }
+
+        let text: &str = "insert 5 0\ninsert 3 0\n\nupdate 1 -\nremove 0 x\n";
+        let err: JournalError = read_journal::<usize, _>(text.as_bytes()).unwrap_err();
+        assert!(err.step() == 3 && err.line() == Some(5));
+        assert!(err.error().kind() == ErrorKind::InvalidJournal);
+        assert!(err.to_string().starts_with("Line 5: "));
+    }
+
+    #[test]
+    pub fn test_file_sink() {
+        let path = std::env::temp_dir().join(format!("rheap-journal-{}.txt", std::process::id()));
+        let mut heap: JournaledHeap<i64, Min, 2, FileSink<_>> =
+            JournaledHeap::new(FileSink::create(&path).unwrap());
+        for n in [4, -2, 9, 0] {
+            heap.insert(n);
+        }
+        heap.top();
+        heap.update(0, |x| *x = 10).unwrap();
+        heap.sink_mut().flush().unwrap();
+
+        let file = std::fs::File::open(&path).unwrap();
+        let journal: Vec<JournalEntry<i64>> = read_journal(BufReader::new(file)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(journal.len() == 6);
+        let rebuilt: Heap<i64, Min, 2> = replay(&journal).unwrap();
+        assert!(rebuilt.as_slice() == heap.as_heap().as_slice());
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{KVHeap, Max, Min, Order};
+
+    const COUNT: usize = 5000;
+
+    #[test]
+    pub fn test_min_kv_heap() {
+        test_kv_heap::<Min, 2>();
+    }
+
+    #[test]
+    pub fn test_max_kv_heap() {
+        test_kv_heap::<Max, 3>();
+    }
+
+    pub fn test_kv_heap<O: Order<u32>, const D: usize>() {
+        let mut rng: StdRng = StdRng::seed_from_u64(D as u64);
+        let v: Vec<(u32, String)> = (0..COUNT)
+            .map(|_| {
+                let k: u32 = rng.gen_range(0..1000);
+                (k, k.to_string())
+            })
+            .collect();
+        let mut heap: KVHeap<u32, String, O, D> = KVHeap::from(v);
+        assert!(heap.len() == COUNT);
+        assert!(heap.is_valid());
+
+        for _ in 0..COUNT {
+            let len: usize = heap.len();
+            match rng.gen_range(0..4) {
+                0 => {
+                    let k: u32 = rng.gen_range(0..1000);
+                    heap.insert(k, k.to_string());
+                }
+                1 => {
+                    let (k, v) = heap.remove(rng.gen_range(0..len)).unwrap();
+                    assert!(
+                        k.to_string() == v,
+                        "heap.remove() separated a key from its value"
+                    );
+                }
+                2 => {
+                    let index: usize = rng.gen_range(0..len);
+                    let k: u32 = rng.gen_range(0..1000);
+                    let old: String = heap.get(index).unwrap().1.clone();
+                    heap.update_key(index, |key| *key = k).unwrap();
+                    let index: usize = heap
+                        .iter()
+                        .position(|(key, v)| *key == k && *v == old)
+                        .unwrap();
+                    heap.update_value(index, |v| *v = k.to_string()).unwrap();
+                }
+                _ => {
+                    let top: u32 = *heap.peek().unwrap().0;
+                    let (k, v) = heap.pop().unwrap();
+                    assert!(k == top && k.to_string() == v);
+                }
+            }
+            assert!(heap.is_valid(), "the heap property was violated");
+        }
+
+        let mut prev: Option<u32> = None;
+        while let Some((k, v)) = heap.pop() {
+            assert!(k.to_string() == v);
+            if let Some(p) = prev {
+                assert!(!O::precedes(&k, &p));
+            }
+            prev = Some(k);
+        }
+        assert!(heap.remove(0).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{LazyHeap, LazyStats, Min};
+    use std::collections::BTreeSet;
+
+    const COUNT: usize = 2000;
+
+    #[test]
+    pub fn test_lazy_heap() {
+        let mut rng: StdRng = StdRng::seed_from_u64(48);
+        let mut heap: LazyHeap<usize, Min, 3> = LazyHeap::with_threshold(0.25);
+        let mut model: BTreeSet<(usize, usize)> = BTreeSet::new();
+        let mut ids: Vec<(usize, usize)> = Vec::new();
+
+        for _ in 0..COUNT {
+            let n: usize = rng.gen_range(0..COUNT);
+            let id: usize = heap.insert(n);
+            model.insert((n, id));
+            ids.push((n, id));
+        }
+        for _ in 0..COUNT / 2 {
+            let (n, id) = ids[rng.gen_range(0..ids.len())];
+            assert!(heap.cancel(id) == model.remove(&(n, id)));
+            assert!(!heap.contains(id));
+            assert!(heap.is_valid());
+            let stats: LazyStats = heap.stats();
+            assert!(stats.live == model.len());
+            assert!((stats.dead as f64) < 0.25 * (stats.live + stats.dead) as f64);
+        }
+        assert!(heap.stats().compactions > 0);
+        assert!(heap.len() == model.len());
+
+        while let Some((n, id)) = heap.peek_with_id().map(|(id, n)| (*n, id)) {
+            let first: (usize, usize) = *model.iter().next().unwrap();
+            assert!(n == first.0);
+            assert!(model.remove(&(n, id)));
+            assert!(heap.top() == Some(n));
+        }
+        assert!(model.is_empty() && heap.is_empty());
+        assert!(heap.stats().live == 0 && heap.stats().dead == 0);
+    }
+
+    #[test]
+    pub fn test_compaction_threshold() {
+        let mut heap: LazyHeap<usize, Min, 2> = LazyHeap::with_threshold(1.0);
+        let ids: Vec<usize> = (0..10).map(|n| heap.insert(n)).collect();
+        for id in ids.iter().skip(1) {
+            assert!(heap.cancel(*id));
+        }
+        assert!(
+            heap.stats()
+                == LazyStats {
+                    live: 1,
+                    dead: 9,
+                    compactions: 0
+                }
+        );
+
+        heap.set_threshold(0.5);
+        assert!(
+            heap.stats()
+                == LazyStats {
+                    live: 1,
+                    dead: 0,
+                    compactions: 1
+                }
+        );
+        assert!(heap.top() == Some(0));
+        assert!(!heap.cancel(ids[0]));
+
+        heap.set_threshold(f64::NAN);
+        assert!(heap.threshold() == 0.5);
+        heap.set_threshold(-3.0);
+        assert!(heap.threshold() == 0.0);
+        let id: usize = heap.insert(5);
+        heap.insert(6);
+        assert!(heap.cancel(id));
+        assert!(
+            heap.stats()
+                == LazyStats {
+                    live: 1,
+                    dead: 0,
+                    compactions: 2
+                }
+        );
+        assert!(heap.peek() == Some(&6));
+    }
+}
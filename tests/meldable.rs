@@ -0,0 +1,110 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{ErrorKind, Max, MeldableHandle, Min, Order, RandomizedMeldableHeap};
+
+    const COUNT: usize = 10000;
+
+    #[test]
+    pub fn test_min_meldable_heap() {
+        test_meldable_heap::<Min>(11);
+    }
+
+    #[test]
+    pub fn test_max_meldable_heap() {
+        test_meldable_heap::<Max>(12);
+    }
+
+    #[test]
+    pub fn test_meldable_heap_is_reproducible() {
+        let mut a: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(99);
+        let mut b: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(99);
+        for n in [5, 3, 8, 1, 9, 2, 7] {
+            a.insert(n);
+            b.insert(n);
+        }
+        assert!(format!("{:?}", a) == format!("{:?}", b));
+    }
+
+    pub fn test_meldable_heap<O: Order<usize>>(seed: u64) {
+        let mut rng: StdRng = StdRng::seed_from_u64(seed);
+        let mut heap: RandomizedMeldableHeap<usize, O> = RandomizedMeldableHeap::with_seed(seed);
+        let mut other: RandomizedMeldableHeap<usize, O> = RandomizedMeldableHeap::with_seed(!seed);
+        let mut model: Vec<usize> = Vec::new();
+
+        for _ in 0..COUNT {
+            let n: usize = rng.gen_range(0..1000);
+            if rng.gen::<bool>() {
+                heap.insert(n);
+            } else {
+                other.insert(n);
+            }
+            model.push(n);
+        }
+        heap.meld(other);
+        assert!(heap.len() == model.len(), "heap.meld() lost elements");
+
+        let mut handles: Vec<(MeldableHandle, usize)> = Vec::new();
+        for _ in 0..COUNT {
+            let n: usize = rng.gen_range(0..1000);
+            handles.push((heap.insert(n), n));
+            model.push(n);
+        }
+
+        handles.shuffle(&mut rng);
+        for (handle, n) in handles.drain(..COUNT / 2) {
+            assert!(heap.remove(handle).unwrap() == n, "heap.remove() returned the wrong element");
+            assert!(heap.remove(handle).is_err(), "stale handle was accepted");
+            let i: usize = model.iter().position(|&m| m == n).unwrap();
+            model.swap_remove(i);
+        }
+        for (handle, n) in handles.iter() {
+            assert!(heap.get(*handle) == Some(n));
+        }
+
+        model.sort_unstable_by(O::compare);
+        for expected in model {
+            assert!(heap.pop() == Some(expected), "heap.pop() returned the wrong element");
+        }
+        assert!(heap.is_empty());
+        assert!(heap.pop().is_none());
+    }
+
+    #[test]
+    pub fn test_meld_keeps_other_handles() {
+        let mut a: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(21);
+        let mut b: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(22);
+        let kept: Vec<MeldableHandle> = (0..10).map(|n| a.insert(n)).collect();
+        let moved: Vec<MeldableHandle> = (10..20).map(|n| b.insert(n)).collect();
+        a.meld(b);
+        assert!(a.len() == 20);
+        for (n, handle) in moved.iter().enumerate() {
+            assert!(a.get(*handle) == Some(&(n + 10)));
+        }
+        for (n, handle) in kept.into_iter().enumerate() {
+            assert!(a.remove(handle).unwrap() == n);
+        }
+        for (n, handle) in moved.iter().enumerate() {
+            assert!(a.remove(*handle).unwrap() == n + 10);
+            assert!(a.remove(*handle).unwrap_err().kind() == ErrorKind::InvalidHandle);
+        }
+        assert!(a.is_empty());
+        a.insert(5);
+        assert!(moved.iter().all(|h| a.get(*h).is_none()));
+    }
+
+    #[test]
+    pub fn test_clone_rejects_foreign_handles() {
+        let mut a: RandomizedMeldableHeap<usize> = RandomizedMeldableHeap::with_seed(23);
+        let ha: MeldableHandle = a.insert(1);
+        let mut b: RandomizedMeldableHeap<usize> = a.clone();
+        assert!(b.len() == 1 && b.peek() == Some(&1));
+        assert!(!b.contains(ha));
+        let hb: MeldableHandle = b.insert(7);
+        a.insert(42);
+        assert!(a.get(hb).is_none());
+        assert!(a.remove(hb).unwrap_err().kind() == ErrorKind::InvalidHandle);
+        assert!(a.len() == 2 && b.get(hb) == Some(&7));
+    }
+}
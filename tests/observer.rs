@@ -0,0 +1,97 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{Heap, HeapObserver, Max, ObservedHeap};
+    use std::collections::HashMap;
+
+    const COUNT: usize = 2000;
+
+    /// Rebuilds the heap's vector from the callbacks alone and tracks the index of every element.
+    #[derive(Debug, Default)]
+    struct Mirror {
+        slots: Vec<usize>,
+        positions: HashMap<usize, usize>,
+        moves: usize,
+    }
+
+    impl HeapObserver<usize> for Mirror {
+        fn on_insert(&mut self, element: &usize, index: usize) {
+            assert!(index == self.slots.len());
+            self.slots.push(*element);
+            self.positions.insert(*element, index);
+        }
+
+        fn on_move(&mut self, element: &usize, _from: usize, to: usize) {
+            self.slots[to] = *element;
+            self.positions.insert(*element, to);
+            self.moves += 1;
+        }
+
+        fn on_remove(&mut self, element: &usize, index: usize) {
+            assert!(index + 1 == self.slots.len() && self.slots[index] == *element);
+            self.slots.pop();
+            self.positions.remove(element);
+        }
+
+        fn on_update(&mut self, old: &usize, new: &usize, index: usize) {
+            assert!(self.slots[index] == *old);
+            self.slots[index] = *new;
+            self.positions.remove(old);
+            self.positions.insert(*new, index);
+        }
+    }
+
+    fn in_sync(heap: &ObservedHeap<usize, Max, 3, Mirror>) -> bool {
+        let slice: &[usize] = heap.as_heap().as_slice();
+        slice == &heap.observer().slots[..]
+            && heap.observer().positions.len() == slice.len()
+            && slice
+                .iter()
+                .enumerate()
+                .all(|(i, x)| heap.observer().positions[x] == i)
+    }
+
+    #[test]
+    pub fn test_observer_stays_in_sync() {
+        let mut rng: StdRng = StdRng::seed_from_u64(53);
+        // distinct values so that the position map is unambiguous
+        let mut values: Vec<usize> = (0..COUNT * 4).collect();
+        values.shuffle(&mut rng);
+        let mut values = values.into_iter();
+
+        let initial: Vec<usize> = values.by_ref().take(COUNT).collect();
+        let mut heap: ObservedHeap<usize, Max, 3, Mirror> =
+            ObservedHeap::from_slice(&initial, Mirror::default());
+        assert!(in_sync(&heap) && heap.observer().moves > 0);
+
+        for _ in 0..COUNT {
+            match rng.gen_range(0..4) {
+                0 => {
+                    heap.insert(values.next().unwrap());
+                }
+                1 => {
+                    heap.top();
+                }
+                2 => {
+                    let index: usize = rng.gen_range(0..heap.len());
+                    heap.remove(index).unwrap();
+                }
+                _ => {
+                    let index: usize = rng.gen_range(0..heap.len());
+                    let value: usize = values.next().unwrap();
+                    heap.update(index, |x| *x = value).unwrap();
+                }
+            }
+            assert!(in_sync(&heap));
+        }
+
+        let more: Vec<usize> = values.by_ref().take(COUNT).collect();
+        let mut other: Heap<usize, Max, 5> = Heap::from(&more[..]);
+        heap.append(&mut other);
+        assert!(other.is_empty() && in_sync(&heap));
+
+        heap.clear();
+        assert!(heap.is_empty() && in_sync(&heap));
+    }
+}
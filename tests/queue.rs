@@ -0,0 +1,83 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{
+        AddressablePriorityQueue, BinaryMaxHeap, CountingHeap, HeapObserver, InstrumentedHeap,
+        JournalEntry, JournaledHeap, Max, Min, ObservedHeap, PriorityQueue, QuinaryMinHeap,
+        RandomizedMeldableHeap, SetHeap, TransactionalHeap,
+    };
+
+    const COUNT: usize = 2000;
+
+    /// An observer that ignores every event.
+    struct Silent;
+
+    impl HeapObserver<usize> for Silent {}
+
+    #[test]
+    pub fn test_priority_queue_impls() {
+        test_priority_queue(QuinaryMinHeap::<usize>::new(), false);
+        test_priority_queue(BinaryMaxHeap::<usize>::new(), true);
+        test_priority_queue(RandomizedMeldableHeap::<usize>::with_seed(1), false);
+        test_priority_queue(RandomizedMeldableHeap::<usize, Max>::with_seed(2), true);
+        test_priority_queue(CountingHeap::<usize, Min, 3>::new(), false);
+        test_priority_queue(TransactionalHeap::<usize, Max, 2>::new(), true);
+        test_priority_queue(InstrumentedHeap::<usize, Min, 4>::new(), false);
+        test_priority_queue(ObservedHeap::<usize, Max, 3, Silent>::new(Silent), true);
+        let sink: Vec<JournalEntry<usize>> = Vec::new();
+        test_priority_queue(JournaledHeap::<usize, Min, 2, _>::new(sink), false);
+    }
+
+    #[test]
+    pub fn test_set_heap_priority_queue() {
+        let mut queue: SetHeap<usize, Min, 2> = SetHeap::new();
+        for n in [4, 1, 4, 3, 1] {
+            queue.push(n);
+        }
+        assert!(PriorityQueue::len(&queue) == 3);
+        assert!(queue.pop() == Some(1) && queue.pop() == Some(3) && queue.pop() == Some(4));
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    pub fn test_addressable_priority_queue_impls() {
+        test_addressable(RandomizedMeldableHeap::<usize>::with_seed(3));
+    }
+
+    fn test_priority_queue<Q: PriorityQueue<usize>>(mut queue: Q, max: bool) {
+        let mut rng: StdRng = StdRng::seed_from_u64(17);
+        let mut model: Vec<usize> = Vec::new();
+        for _ in 0..COUNT {
+            let n: usize = rng.gen_range(0..500);
+            queue.push(n);
+            model.push(n);
+        }
+        assert!(queue.len() == COUNT);
+        model.sort_unstable();
+        if max {
+            model.reverse();
+        }
+        for expected in model.iter().take(COUNT / 2) {
+            assert!(queue.peek() == Some(expected));
+            assert!(queue.pop() == Some(*expected));
+        }
+        queue.clear();
+        assert!(queue.is_empty());
+        assert!(queue.pop().is_none());
+    }
+
+    fn test_addressable<Q: AddressablePriorityQueue<usize>>(mut queue: Q) {
+        for n in [10, 20, 30, 40, 50] {
+            queue.push(n);
+        }
+        let handle: Q::Handle = queue.push_with_handle(35);
+        assert!(queue.get(handle) == Some(&35));
+        let handle: Q::Handle = queue.update(handle, |x| *x = 5).unwrap();
+        assert!(queue.get(handle) == Some(&5));
+        assert!(queue.peek() == Some(&5));
+        assert!(queue.remove(handle).unwrap() == 5);
+        assert!(queue.pop() == Some(10));
+        assert!(queue.len() == 4);
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{CountingHeap, Max, Min, Order, SetHeap};
+    use std::cmp::Ordering;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::hash::{Hash, Hasher};
+
+    const COUNT: usize = 2000;
+
+    #[test]
+    pub fn test_set_heap() {
+        let mut rng: StdRng = StdRng::seed_from_u64(46);
+        let mut heap: SetHeap<usize, Min, 3> = SetHeap::new();
+        let mut model: BTreeSet<usize> = BTreeSet::new();
+        for _ in 0..COUNT {
+            let n: usize = rng.gen_range(0..COUNT / 4);
+            assert!(heap.insert(n) == model.insert(n));
+            assert!(heap.contains(&n));
+        }
+        assert!(heap.len() == model.len());
+        assert!(heap.as_heap().is_valid());
+
+        for _ in 0..COUNT / 8 {
+            let n: usize = rng.gen_range(0..COUNT / 4);
+            assert!(heap.remove(&n) == model.remove(&n));
+            assert!(!heap.contains(&n));
+        }
+        assert!(heap.len() == model.len());
+
+        for expected in model.iter() {
+            assert!(heap.peek() == Some(expected));
+            assert!(heap.top() == Some(*expected));
+            assert!(!heap.contains(expected));
+        }
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    pub fn test_counting_heap() {
+        let mut rng: StdRng = StdRng::seed_from_u64(47);
+        let mut heap: CountingHeap<usize, Max, 2> = CountingHeap::new();
+        let mut model: BTreeMap<usize, usize> = BTreeMap::new();
+        for _ in 0..COUNT {
+            let n: usize = rng.gen_range(0..50);
+            let count: &mut usize = model.entry(n).or_insert(0);
+            *count += 1;
+            assert!(heap.insert(n) == *count);
+        }
+        assert!(heap.len() == COUNT && heap.distinct_len() == model.len());
+        assert!(heap.iter().all(|(v, c)| model[v] == c));
+
+        let removed: usize = heap.remove_all(&25);
+        assert!(removed == model.remove(&25).unwrap_or(0));
+        assert!(heap.count(&25) == 0 && heap.remove_all(&25) == 0);
+        assert!(heap.len() == COUNT - removed);
+
+        while let Some(n) = heap.top() {
+            let (&expected, count) = model.iter_mut().next_back().unwrap();
+            assert!(n == expected);
+            *count -= 1;
+            if *count == 0 {
+                model.remove(&expected);
+            }
+            assert!(heap.count(&n) == model.get(&n).copied().unwrap_or(0));
+        }
+        assert!(heap.is_empty() && model.is_empty());
+    }
+
+    /// A task whose identity is its `id` while the heap orders it by `pri`,
+    /// so `Eq` is coarser than the ordering policy.
+    #[derive(Debug, Clone, Copy)]
+    struct Task {
+        id: u32,
+        pri: u32,
+    }
+
+    impl PartialEq for Task {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for Task {}
+
+    impl Hash for Task {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.id.hash(state);
+        }
+    }
+
+    struct ByPri;
+
+    impl Order<Task> for ByPri {
+        fn compare(a: &Task, b: &Task) -> Ordering {
+            a.pri.cmp(&b.pri)
+        }
+    }
+
+    #[test]
+    pub fn test_eq_coarser_than_order() {
+        let mut set: SetHeap<Task, ByPri, 2> = SetHeap::new();
+        let mut counting: CountingHeap<Task, ByPri, 2> = CountingHeap::new();
+        for id in 0..100 {
+            let task: Task = Task { id, pri: 100 - id };
+            assert!(set.insert(task));
+            counting.insert(task);
+            counting.insert(task);
+        }
+        for id in (0..100).step_by(3) {
+            let key: Task = Task { id, pri: 0 };
+            assert!(set.remove(&key));
+            assert!(!set.remove(&key));
+            assert!(counting.remove_all(&key) == 2);
+            assert!(counting.remove_all(&key) == 0);
+        }
+        assert!(set.len() == 66 && counting.distinct_len() == 66 && counting.len() == 132);
+        assert!(set.as_heap().is_valid());
+        assert!(set.top().map(|t| t.id) == Some(98));
+        assert!(counting.top().map(|t| t.id) == Some(98));
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{Fifo, Lifo, Order, Random, StableHeap, TieBreak};
+    use std::cmp::Ordering;
+
+    const COUNT: usize = 2000;
+
+    /// A job whose priority is the only thing the heap orders by.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Job {
+        priority: u8,
+        id: usize,
+    }
+
+    #[derive(Debug, Clone)]
+    struct ByPriority;
+
+    impl Order<Job> for ByPriority {
+        fn compare(a: &Job, b: &Job) -> Ordering {
+            a.priority.cmp(&b.priority)
+        }
+    }
+
+    fn drain<B: TieBreak>(heap: &mut StableHeap<Job, ByPriority, 3, B>) -> Vec<Job> {
+        let mut v: Vec<Job> = Vec::new();
+        while let Some(job) = heap.top() {
+            v.push(job);
+        }
+        v
+    }
+
+    fn jobs(rng: &mut StdRng, first_id: usize) -> Vec<Job> {
+        (0..COUNT)
+            .map(|i| Job {
+                priority: rng.gen_range(0..8),
+                id: first_id + i,
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn test_fifo_and_lifo() {
+        let mut rng: StdRng = StdRng::seed_from_u64(43);
+        let v: Vec<Job> = jobs(&mut rng, 0);
+
+        let mut fifo: StableHeap<Job, ByPriority, 3, Fifo> = StableHeap::new();
+        let mut lifo: StableHeap<Job, ByPriority, 3, Lifo> = StableHeap::new();
+        for job in v.iter() {
+            fifo.insert(*job);
+            lifo.insert(*job);
+        }
+        assert!(fifo.is_valid() && lifo.is_valid());
+
+        let mut expected: Vec<Job> = v.clone();
+        expected.sort_by_key(|j| (j.priority, j.id));
+        assert!(drain(&mut fifo) == expected);
+        expected.sort_by_key(|j| (j.priority, std::cmp::Reverse(j.id)));
+        assert!(drain(&mut lifo) == expected);
+
+        let mut from: StableHeap<Job, ByPriority, 3, Fifo> = StableHeap::from(&v[..]);
+        expected.sort_by_key(|j| (j.priority, j.id));
+        assert!(drain(&mut from) == expected);
+    }
+
+    #[test]
+    pub fn test_stability_across_mutations() {
+        let mut rng: StdRng = StdRng::seed_from_u64(44);
+        let v: Vec<Job> = jobs(&mut rng, 0);
+        let mut heap: StableHeap<Job, ByPriority, 3, Fifo> = StableHeap::from(&v[..]);
+        let mut model: Vec<Job> = v.clone();
+
+        for _ in 0..COUNT / 4 {
+            let index: usize = rng.gen_range(0..heap.len());
+            let removed: Job = heap.remove(index).unwrap();
+            model.retain(|j| j.id != removed.id);
+
+            let index: usize = rng.gen_range(0..heap.len());
+            let priority: u8 = rng.gen_range(0..8);
+            let id: usize = heap.iter().nth(index).unwrap().id;
+            let new_index: usize = heap.update(index, |j| j.priority = priority).unwrap();
+            assert!(heap.iter().nth(new_index).unwrap().id == id);
+            model.iter_mut().find(|j| j.id == id).unwrap().priority = priority;
+            assert!(heap.is_valid());
+        }
+
+        let w: Vec<Job> = jobs(&mut rng, COUNT);
+        let mut other: StableHeap<Job, ByPriority, 3, Fifo> = StableHeap::new();
+        for job in w.iter() {
+            other.insert(*job);
+        }
+        heap.append(&mut other);
+        assert!(other.is_empty() && heap.is_valid());
+        model.extend(w.iter().copied());
+
+        // every job keeps its submission order among jobs of equal priority
+        model.sort_by_key(|j| (j.priority, j.id));
+        assert!(drain(&mut heap) == model);
+    }
+
+    #[test]
+    pub fn test_seeded_random_ties() {
+        let mut rng: StdRng = StdRng::seed_from_u64(45);
+        let v: Vec<Job> = jobs(&mut rng, 0);
+        let mut a: StableHeap<Job, ByPriority, 3, Random> = StableHeap::with_seed(7);
+        let mut b: StableHeap<Job, ByPriority, 3, Random> = StableHeap::with_seed(7);
+        let mut c: StableHeap<Job, ByPriority, 3, Random> = StableHeap::with_seed(8);
+        for job in v.iter() {
+            a.insert(*job);
+            b.insert(*job);
+            c.insert(*job);
+        }
+        let (a, b, c) = (drain(&mut a), drain(&mut b), drain(&mut c));
+        assert!(a == b && a != c);
+        assert!(a.windows(2).all(|w| w[0].priority <= w[1].priority));
+    }
+}
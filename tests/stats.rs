@@ -0,0 +1,101 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{Heap, HeapStats, InstrumentedHeap, OpStats, Order};
+    use std::cell::Cell;
+    use std::cmp::Ordering;
+
+    const COUNT: usize = 2000;
+
+    thread_local! {
+        static COMPARISONS: Cell<u64> = const { Cell::new(0) };
+    }
+
+    /// A minimum heap policy that counts how often it is asked to compare.
+    #[derive(Debug, Clone)]
+    struct CountingMin;
+
+    impl Order<usize> for CountingMin {
+        fn compare(a: &usize, b: &usize) -> Ordering {
+            COMPARISONS.with(|c| c.set(c.get() + 1));
+            a.cmp(b)
+        }
+    }
+
+    fn comparisons() -> u64 {
+        COMPARISONS.with(|c| c.replace(0))
+    }
+
+    fn test_counts<const D: usize>() {
+        let mut rng: StdRng = StdRng::seed_from_u64(54);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..COUNT)).collect();
+        comparisons();
+
+        let mut heap: InstrumentedHeap<usize, CountingMin, D> = InstrumentedHeap::from_slice(&v);
+        assert!(heap.stats().rebuild.comparisons == comparisons());
+        for _ in 0..COUNT {
+            heap.insert(rng.gen_range(0..COUNT));
+        }
+        assert!(heap.stats().insert.comparisons == comparisons());
+        for _ in 0..COUNT / 2 {
+            heap.top();
+        }
+        assert!(heap.stats().top.comparisons == comparisons());
+        for _ in 0..COUNT / 2 {
+            heap.remove(rng.gen_range(0..heap.len())).unwrap();
+        }
+        assert!(heap.stats().remove.comparisons == comparisons());
+        for _ in 0..COUNT / 2 {
+            let value: usize = rng.gen_range(0..COUNT);
+            heap.update(rng.gen_range(0..heap.len()), |x| *x = value)
+                .unwrap();
+        }
+        assert!(heap.stats().update.comparisons == comparisons());
+        let mut other: Heap<usize, CountingMin, 2> = Heap::new();
+        other.insert(0);
+        comparisons();
+        let before: u64 = heap.stats().rebuild.comparisons;
+        heap.append(&mut other);
+        assert!(heap.stats().rebuild.comparisons - before == comparisons());
+        assert!(heap.as_heap().is_valid());
+
+        let stats: HeapStats = heap.stats();
+        assert!(stats.insert.calls == COUNT as u64 && stats.rebuild.calls == 2);
+        // sifting up or down swaps once per level, and removal swaps the last element into place first
+        assert!(stats.insert.swaps == stats.insert.levels);
+        assert!(stats.update.swaps == stats.update.levels);
+        assert!(
+            stats.top.swaps >= stats.top.levels
+                && stats.top.swaps <= stats.top.levels + stats.top.calls
+        );
+        let total: OpStats = stats.total();
+        assert!(total.comparisons >= total.swaps && total.swaps >= total.levels);
+
+        heap.reset_stats();
+        assert!(heap.stats() == HeapStats::default());
+        assert!(heap.stats().top.comparisons_per_call() == 0.0);
+    }
+
+    #[test]
+    pub fn test_instrumented_heap() {
+        test_counts::<2>();
+        test_counts::<4>();
+        test_counts::<7>();
+    }
+
+    #[test]
+    pub fn test_arity_comparison() {
+        let mut binary: InstrumentedHeap<usize, CountingMin, 2> = InstrumentedHeap::new();
+        let mut quaternary: InstrumentedHeap<usize, CountingMin, 4> = InstrumentedHeap::new();
+        for n in (0..COUNT).rev() {
+            binary.insert(n);
+            quaternary.insert(n);
+        }
+        while binary.top().is_some() && quaternary.top().is_some() {}
+        let (b, q): (HeapStats, HeapStats) = (binary.stats(), quaternary.stats());
+        assert!(q.insert.levels_per_call() < b.insert.levels_per_call());
+        assert!(q.top.levels < b.top.levels);
+        assert!(q.top.comparisons_per_call() > 0.0 && b.top.swaps_per_call() > 0.0);
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+pub mod test {
+
+    use rand::prelude::*;
+    use rheap::{Checkpoint, ErrorKind, Heap, Max, TransactionalHeap};
+
+    const COUNT: usize = 2000;
+
+    fn random_ops(heap: &mut TransactionalHeap<usize, Max, 3>, rng: &mut StdRng, n: usize) {
+        for _ in 0..n {
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    heap.insert(rng.gen_range(0..COUNT));
+                }
+                2 => {
+                    heap.top();
+                }
+                _ if !heap.is_empty() => {
+                    let index: usize = rng.gen_range(0..heap.len());
+                    let value: usize = rng.gen_range(0..COUNT);
+                    heap.update(index, |x| *x = value).unwrap();
+                }
+                _ => (),
+            }
+            assert!(heap.as_heap().is_valid());
+        }
+    }
+
+    #[test]
+    pub fn test_nested_rollback() {
+        let mut rng: StdRng = StdRng::seed_from_u64(49);
+        let v: Vec<usize> = (0..COUNT).map(|_| rng.gen_range(0..COUNT)).collect();
+        let mut heap: TransactionalHeap<usize, Max, 3> =
+            TransactionalHeap::from(Heap::from(&v[..]));
+
+        let mut snapshots: Vec<(Checkpoint, Vec<usize>)> = Vec::new();
+        for _ in 0..5 {
+            let snapshot: Vec<usize> = heap.as_heap().as_slice().to_vec();
+            snapshots.push((heap.checkpoint(), snapshot));
+            random_ops(&mut heap, &mut rng, COUNT / 4);
+        }
+        assert!(heap.depth() == 5);
+
+        let (token, snapshot) = snapshots.pop().unwrap();
+        heap.rollback(token).unwrap();
+        assert!(heap.as_heap().as_slice() == &snapshot[..]);
+
+        let (token, _) = snapshots.pop().unwrap();
+        let committed: Vec<usize> = heap.as_heap().as_slice().to_vec();
+        heap.commit(token).unwrap();
+        assert!(heap.as_heap().as_slice() == &committed[..]);
+        assert!(heap.depth() == 3);
+
+        // rolling back an outer checkpoint also closes the ones inside it
+        let (inner, _) = snapshots.pop().unwrap();
+        let (outer, snapshot) = snapshots.pop().unwrap();
+        random_ops(&mut heap, &mut rng, COUNT / 4);
+        heap.rollback(outer).unwrap();
+        assert!(heap.as_heap().as_slice() == &snapshot[..]);
+        assert!(heap.depth() == 1);
+        assert!(heap.rollback(inner).unwrap_err().kind() == ErrorKind::InvalidCheckpoint);
+
+        let (token, snapshot) = snapshots.pop().unwrap();
+        heap.rollback(token).unwrap();
+        assert!(heap.as_heap().as_slice() == Heap::<usize, Max, 3>::from(&v[..]).as_slice());
+        assert!(snapshot == heap.as_heap().as_slice());
+        assert!(heap.depth() == 0);
+    }
+
+    #[test]
+    pub fn test_commit_without_checkpoints() {
+        let mut rng: StdRng = StdRng::seed_from_u64(50);
+        let mut heap: TransactionalHeap<usize, Max, 3> = TransactionalHeap::new();
+        random_ops(&mut heap, &mut rng, COUNT);
+        let token: Checkpoint = heap.checkpoint();
+        random_ops(&mut heap, &mut rng, COUNT);
+        let kept: Vec<usize> = heap.as_heap().as_slice().to_vec();
+        heap.commit(token).unwrap();
+        let token: Checkpoint = heap.checkpoint();
+        assert!(heap.remove(heap.len()).unwrap_err().kind() == ErrorKind::InvalidIndex);
+        heap.rollback(token).unwrap();
+        assert!(heap.as_heap().as_slice() == &kept[..]);
+        let token: Checkpoint = heap.checkpoint();
+        heap.clear();
+        assert!(heap.is_empty());
+        heap.rollback(token).unwrap();
+        assert!(heap.as_heap().as_slice() == &kept[..]);
+    }
+
+    #[test]
+    pub fn test_foreign_checkpoint() {
+        let mut a: TransactionalHeap<usize, Max, 3> = TransactionalHeap::new();
+        let mut b: TransactionalHeap<usize, Max, 3> = TransactionalHeap::new();
+        a.insert(1);
+        let token: Checkpoint = a.checkpoint();
+        a.insert(2);
+        let foreign: Checkpoint = b.checkpoint();
+        b.insert(3);
+
+        // both tokens have the same level, but each belongs to its own heap
+        let err = a.rollback(foreign).unwrap_err();
+        assert!(err.kind() == ErrorKind::InvalidCheckpoint);
+        assert!(a.depth() == 1 && a.len() == 2);
+
+        // a clone keeps the elements but not the checkpoints of the original
+        let mut clone: TransactionalHeap<usize, Max, 3> = a.clone();
+        assert!(clone.depth() == 0 && clone.len() == 2);
+        let inner: Checkpoint = a.checkpoint();
+        let err = clone.commit(inner).unwrap_err();
+        assert!(err.kind() == ErrorKind::InvalidCheckpoint);
+        assert!(a.depth() == 2);
+
+        a.rollback(token).unwrap();
+        assert!(a.as_heap().as_slice() == [1]);
+        assert!(b.depth() == 1 && b.len() == 1);
+    }
+}